@@ -1,4 +1,5 @@
 use dotenvy::dotenv;
+use max_bot_api_client_rs::dispatcher::Dispatcher;
 use max_bot_api_client_rs::message::MessageExt;
 use std::env;
 
@@ -17,17 +18,8 @@ async fn handle_message(client: MaxBotClient, message: Message) -> Result<()> {
     Ok(())
 }
 
-async fn handle_update(client: MaxBotClient, update: Update) -> Result<()> {
-    match update {
-        Update::MessageCreated(message_update) => {
-            let message = message_update.message;
-
-            handle_message(client, message).await?
-        }
-        _ => {
-            println!("Необработанный тип события: {:?}", update);
-        }
-    }
+async fn handle_update(update: Update) -> Result<()> {
+    println!("Необработанный тип события: {:?}", update);
     Ok(())
 }
 
@@ -38,20 +30,27 @@ async fn main() -> Result<()> {
     let access_token = env::var("ACCESS_TOKEN").expect("TOKEN not set");
     let client = MaxBotClient::new(access_token);
 
-    let (mut event_handler, mut event_receiver) = EventHandler::new(client.clone());
+    let (mut event_handler, event_receiver) = EventHandler::new(client.clone());
+    event_handler.on_error(|e| eprintln!("Ошибка опроса ({:?}): {}", e.kind, e.error));
 
-    let handler_task = tokio::spawn(async move {
-        if let Err(e) = event_handler.start().await {
-            eprintln!("Ошибка в обработчике событий: {}", e);
+    let shutdown = event_handler.shutdown_handle();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            shutdown.shutdown();
         }
     });
 
-    while let Some(update) = event_receiver.recv().await {
-        if let Err(e) = handle_update(client.clone(), update).await {
-            eprintln!("Ошибка обработки обновления: {}", e);
-            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    let handler_task = tokio::spawn(async move {
+        match event_handler.start().await {
+            Ok(marker) => println!("Опрос остановлен, маркер: {:?}", marker),
+            Err(e) => eprintln!("Ошибка в обработчике событий: {}", e),
         }
-    }
+    });
+
+    let mut dispatcher = Dispatcher::new(client);
+    dispatcher.on_message_created(handle_message);
+    dispatcher.fallback(handle_update);
+    dispatcher.run(event_receiver).await;
 
     if let Err(e) = handler_task.await {
         eprintln!("Ошибка в задаче обработчика: {:?}", e);
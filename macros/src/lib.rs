@@ -0,0 +1,215 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Attribute, Data, DeriveInput, Fields, LitStr, Variant, parse_macro_input};
+
+#[proc_macro_derive(BotCommands, attributes(command))]
+pub fn derive_bot_commands(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lowercase,
+    SnakeCase,
+}
+
+impl RenameRule {
+    fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::Lowercase => name.to_lowercase(),
+            RenameRule::SnakeCase => {
+                let chars: Vec<char> = name.chars().collect();
+                let mut result = String::new();
+
+                // Подчёркивание ставится на границе слов, серия заглавных считается одним словом
+                for (i, &c) in chars.iter().enumerate() {
+                    if c.is_uppercase() && i > 0 {
+                        let prev = chars[i - 1];
+                        let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+
+                        if !prev.is_uppercase() || next_is_lower {
+                            result.push('_');
+                        }
+                    }
+                    result.extend(c.to_lowercase());
+                }
+                result
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct VariantAttrs {
+    rename: Option<String>,
+    description: Option<String>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "BotCommands can only be derived for enums",
+        ));
+    };
+
+    let rename_rule = parse_enum_attrs(&input.attrs)?;
+
+    let mut parse_arms = Vec::new();
+    let mut bot_commands = Vec::new();
+
+    for variant in &data.variants {
+        let attrs = parse_variant_attrs(&variant.attrs)?;
+        let name = attrs
+            .rename
+            .unwrap_or_else(|| rename_rule.apply(&variant.ident.to_string()));
+        let description = match attrs.description {
+            Some(description) => quote!(::std::option::Option::Some(#description.to_string())),
+            None => quote!(::std::option::Option::None),
+        };
+
+        let constructor = variant_constructor(variant);
+        parse_arms.push(quote! {
+            if command.name.eq_ignore_ascii_case(#name) {
+                return #constructor;
+            }
+        });
+
+        bot_commands.push(quote! {
+            ::max_bot_api_client_rs::api::models::BotCommand {
+                name: #name.to_string(),
+                description: #description,
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::max_bot_api_client_rs::command::BotCommands for #ident #ty_generics #where_clause {
+            fn parse(
+                command: &::max_bot_api_client_rs::command::ParsedCommand,
+            ) -> ::std::result::Result<Self, ::max_bot_api_client_rs::command::CommandError> {
+                #(#parse_arms)*
+
+                ::std::result::Result::Err(
+                    ::max_bot_api_client_rs::command::CommandError::UnknownCommand(
+                        command.name.clone(),
+                    ),
+                )
+            }
+
+            fn bot_commands() -> ::std::vec::Vec<::max_bot_api_client_rs::api::models::BotCommand> {
+                ::std::vec![#(#bot_commands),*]
+            }
+        }
+    })
+}
+
+// Единственное поле получает исходный текст аргументов, несколько полей — по одному аргументу
+fn variant_constructor(variant: &Variant) -> TokenStream2 {
+    let ident = &variant.ident;
+    let types: Vec<_> = variant.fields.iter().map(|field| &field.ty).collect();
+    let bindings: Vec<_> = (0..types.len())
+        .map(|i| format_ident!("arg{}", i))
+        .collect();
+
+    let construct = match &variant.fields {
+        Fields::Unit => quote!(Self::#ident),
+        Fields::Unnamed(_) => quote!(Self::#ident(#(#bindings),*)),
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!(Self::#ident { #(#names: #bindings),* })
+        }
+    };
+
+    match types.len() {
+        0 => quote!(::std::result::Result::Ok(#construct)),
+        1 => {
+            let binding = &bindings[0];
+            quote! {{
+                let #binding = ::max_bot_api_client_rs::command::parse_arg(0, &command.raw_args)?;
+                ::std::result::Result::Ok(#construct)
+            }}
+        }
+        _ => quote! {{
+            let (#(#bindings,)*) = <(#(#types,)*) as ::max_bot_api_client_rs::command::CommandArgs>::parse_args(&command.args)?;
+            ::std::result::Result::Ok(#construct)
+        }},
+    }
+}
+
+fn parse_enum_attrs(attrs: &[Attribute]) -> syn::Result<RenameRule> {
+    let mut rule = RenameRule::Lowercase;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("command")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                let value: LitStr = meta.value()?.parse()?;
+                rule = match value.value().as_str() {
+                    "lowercase" => RenameRule::Lowercase,
+                    "snake_case" => RenameRule::SnakeCase,
+                    _ => return Err(meta.error("expected \"lowercase\" or \"snake_case\"")),
+                };
+                Ok(())
+            } else {
+                Err(meta.error("unsupported command attribute"))
+            }
+        })?;
+    }
+
+    Ok(rule)
+}
+
+fn parse_variant_attrs(attrs: &[Attribute]) -> syn::Result<VariantAttrs> {
+    let mut result = VariantAttrs::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("command")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let value: LitStr = meta.value()?.parse()?;
+                result.rename = Some(value.value());
+                Ok(())
+            } else if meta.path.is_ident("description") {
+                let value: LitStr = meta.value()?.parse()?;
+                result.description = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported command attribute"))
+            }
+        })?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenameRule;
+
+    #[test]
+    fn snake_case_splits_words_and_acronyms() {
+        let cases = [
+            ("Start", "start"),
+            ("SetName", "set_name"),
+            ("HTTPServer", "http_server"),
+            ("GetURL", "get_url"),
+            ("Api2Key", "api2_key"),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(RenameRule::SnakeCase.apply(name), expected);
+        }
+    }
+
+    #[test]
+    fn lowercase_keeps_words_together() {
+        assert_eq!(RenameRule::Lowercase.apply("SetName"), "setname");
+    }
+}
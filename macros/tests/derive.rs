@@ -0,0 +1,120 @@
+use max_bot_api_client_rs::api::models::BotCommand;
+use max_bot_api_client_rs::command::{BotCommands, CommandError, ParsedCommand};
+
+#[derive(Debug, PartialEq, BotCommands)]
+enum Command {
+    #[command(description = "Start the bot")]
+    Start,
+    #[command(rename = "say", description = "Repeat the text")]
+    Echo(String),
+    Add(i32, i32),
+    Ban {
+        user_id: i64,
+        days: u32,
+    },
+}
+
+#[derive(Debug, PartialEq, BotCommands)]
+#[command(rename_all = "snake_case")]
+enum SnakeCommand {
+    SetName(String),
+    HTTPStatus,
+}
+
+fn parse<C: BotCommands>(text: &str) -> Result<C, CommandError> {
+    C::parse(&ParsedCommand::parse(text).unwrap())
+}
+
+#[test]
+fn parses_unit_variant() {
+    assert_eq!(parse::<Command>("/start").unwrap(), Command::Start);
+    assert_eq!(parse::<Command>("/START").unwrap(), Command::Start);
+}
+
+#[test]
+fn single_field_takes_raw_argument_text() {
+    assert_eq!(
+        parse::<Command>("/say hello  \"big world\" don't \\n").unwrap(),
+        Command::Echo("hello  \"big world\" don't \\n".to_string())
+    );
+}
+
+#[test]
+fn tuple_fields_are_parsed_by_position() {
+    assert_eq!(parse::<Command>("/add 2 40").unwrap(), Command::Add(2, 40));
+}
+
+#[test]
+fn named_fields_are_parsed_in_declaration_order() {
+    assert_eq!(
+        parse::<Command>("/ban 42 7").unwrap(),
+        Command::Ban {
+            user_id: 42,
+            days: 7
+        }
+    );
+}
+
+#[test]
+fn rename_all_snake_case() {
+    assert_eq!(
+        parse::<SnakeCommand>("/set_name Bob").unwrap(),
+        SnakeCommand::SetName("Bob".to_string())
+    );
+    assert_eq!(
+        parse::<SnakeCommand>("/http_status").unwrap(),
+        SnakeCommand::HTTPStatus
+    );
+}
+
+#[test]
+fn reports_parse_errors() {
+    assert!(matches!(
+        parse::<Command>("/unknown"),
+        Err(CommandError::UnknownCommand(name)) if name == "unknown"
+    ));
+    assert!(matches!(
+        parse::<Command>("/add 1"),
+        Err(CommandError::ArgumentCount {
+            expected: 2,
+            found: 1
+        })
+    ));
+    assert!(matches!(
+        parse::<Command>("/add 1 x"),
+        Err(CommandError::InvalidArgument { index: 1, .. })
+    ));
+}
+
+#[test]
+fn generates_bot_commands() {
+    let commands = Command::bot_commands();
+
+    assert_eq!(
+        commands,
+        [
+            BotCommand {
+                name: "start".to_string(),
+                description: Some("Start the bot".to_string()),
+            },
+            BotCommand {
+                name: "say".to_string(),
+                description: Some("Repeat the text".to_string()),
+            },
+            BotCommand {
+                name: "add".to_string(),
+                description: None,
+            },
+            BotCommand {
+                name: "ban".to_string(),
+                description: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn compile_errors() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/*.rs");
+}
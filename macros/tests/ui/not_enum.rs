@@ -0,0 +1,6 @@
+use max_bot_api_client_rs::command::BotCommands;
+
+#[derive(BotCommands)]
+struct Command;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use max_bot_api_client_rs::command::BotCommands;
+
+#[derive(BotCommands)]
+enum Command {
+    #[command(alias = "begin")]
+    Start,
+}
+
+fn main() {}
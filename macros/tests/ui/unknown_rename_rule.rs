@@ -0,0 +1,9 @@
+use max_bot_api_client_rs::command::BotCommands;
+
+#[derive(BotCommands)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    Start,
+}
+
+fn main() {}
@@ -1,9 +1,9 @@
-use crate::api::models::{NewMessageBody, TextFormat};
+use crate::api::models::{AttachmentRequest, NewMessageBody, TextFormat};
 
 impl NewMessageBody {
     pub fn new(text: String) -> Self {
         Self {
-            text: Some(text.into()),
+            text: Some(text),
             attachments: None,
             link: None,
             notify: Some(false),
@@ -14,4 +14,10 @@ impl NewMessageBody {
     pub fn set_notify(&mut self, notify: bool) {
         self.notify = Some(notify);
     }
+
+    pub fn add_attachment(&mut self, attachment: AttachmentRequest) {
+        self.attachments
+            .get_or_insert_with(Vec::new)
+            .push(attachment);
+    }
 }
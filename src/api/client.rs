@@ -1,26 +1,79 @@
-use reqwest::{Client, Method, Response, Url};
+use reqwest::{Client, Method, Request, Response, Url};
 use serde::Serialize;
 use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::time::sleep;
+use tower::{ServiceExt, util::BoxCloneSyncService};
 
 use super::error::ApiClientError;
-use crate::{api::models::ApiError, api::types::Result};
+use super::rate_limit::{RateLimitOptions, RateLimiter};
+use super::retry::{RetryPolicy, is_transient_status};
+use super::service::{HttpService, ReqwestService};
+use crate::{
+    api::models::{ApiError, AttachmentWaitOptions},
+    api::types::Result,
+};
 
 #[derive(Debug, Clone)]
 pub struct ApiClient {
-    http_client: Client,
+    pub(super) http_client: Client,
+    service: HttpService,
     base_url: Url,
     access_token: String,
+    pub(super) attachment_wait: AttachmentWaitOptions,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl ApiClient {
     pub fn new(access_token: String, base_url: Url) -> Self {
+        let http_client = Client::new();
+        let service = BoxCloneSyncService::new(ReqwestService::new(http_client.clone()));
+
+        Self::with_service(access_token, base_url, http_client, service)
+    }
+
+    pub fn with_service(
+        access_token: String,
+        base_url: Url,
+        http_client: Client,
+        service: HttpService,
+    ) -> Self {
         Self {
-            http_client: Client::new(),
+            http_client,
+            service,
             base_url,
             access_token,
+            attachment_wait: AttachmentWaitOptions::default(),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
         }
     }
 
+    pub fn set_attachment_wait(&mut self, options: AttachmentWaitOptions) {
+        self.attachment_wait = options;
+    }
+
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    // Клоны клиента разделяют один ограничитель и общий бюджет запросов
+    pub fn set_rate_limit(&mut self, options: Option<RateLimitOptions>) {
+        self.rate_limiter = options.map(|options| Arc::new(RateLimiter::new(options)));
+    }
+
+    pub fn rate_limiter(&self) -> Option<&Arc<RateLimiter>> {
+        self.rate_limiter.as_ref()
+    }
+
+    // Копия клиента с другой политикой повторов для отдельных вызовов
+    pub fn with_retry_policy(&self, policy: RetryPolicy) -> Self {
+        let mut client = self.clone();
+        client.retry_policy = policy;
+        client
+    }
+
     pub async fn send_request<T: Serialize>(
         &self,
         method: Method,
@@ -47,11 +100,20 @@ impl ApiClient {
             request
         };
 
-        let response = request.send().await?;
+        let response = self.execute_with_retry(request.build()?).await?;
 
         if !response.status().is_success() {
-            let error: ApiError = response.json().await?;
+            let status = response.status();
+            let body = response.text().await?;
+
+            // Прокси и балансировщики могут вернуть ошибку не в формате API
+            let error = serde_json::from_str(&body).unwrap_or_else(|_| ApiError {
+                code: status.as_str().to_string(),
+                message: body,
+            });
+
             return Err(ApiClientError::ApiError {
+                status: status.as_u16(),
                 code: error.code,
                 message: error.message,
             });
@@ -59,4 +121,51 @@ impl ApiClient {
 
         Ok(response)
     }
+
+    async fn execute_with_retry(&self, mut request: Request) -> Result<Response> {
+        let policy = &self.retry_policy;
+        let method_allowed = policy.allows_method(request.method());
+        let mut attempt = 0;
+
+        loop {
+            // Запросы с потоковым телом клонировать нельзя, они не повторяются
+            let next_request = if method_allowed && attempt < policy.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            if let Some(limiter) = &self.rate_limiter {
+                limiter.acquire().await;
+            }
+
+            let result = self.execute(request).await;
+
+            let Some(next_request) = next_request else {
+                return result;
+            };
+
+            let delay = match &result {
+                Ok(response) if is_transient_status(response.status()) => {
+                    policy.retry_delay(response.headers(), attempt)
+                }
+                Err(e) if e.is_transient() => policy.backoff(attempt),
+                _ => return result,
+            };
+
+            sleep(delay).await;
+            request = next_request;
+            attempt += 1;
+        }
+    }
+
+    pub(super) async fn acquire_chat(&self, chat_id: Option<i64>) {
+        if let (Some(limiter), Some(chat_id)) = (&self.rate_limiter, chat_id) {
+            limiter.acquire_chat(chat_id).await;
+        }
+    }
+
+    pub(super) async fn execute(&self, request: Request) -> Result<Response> {
+        self.service.clone().oneshot(request).await
+    }
 }
@@ -1,9 +1,12 @@
-use reqwest::Error as ReqwestError;
+use reqwest::{Error as ReqwestError, StatusCode};
+use serde_json::Error as JsonError;
+use std::io::Error as IoError;
 use thiserror::Error;
 use tokio::sync::mpsc::error::SendError;
+use tower::{BoxError, load_shed::error::Overloaded, timeout::error::Elapsed};
 use url::ParseError;
 
-use crate::api::models::Update;
+use crate::api::{models::Update, retry::is_transient_status};
 
 #[derive(Debug, Error)]
 pub enum ApiClientError {
@@ -11,11 +14,74 @@ pub enum ApiClientError {
     RequestError(#[from] ReqwestError),
 
     #[error("API error: {message} (code: {code})")]
-    ApiError { code: String, message: String },
+    ApiError {
+        status: u16,
+        code: String,
+        message: String,
+    },
 
     #[error("URL parse error: {0}")]
     UrlParseError(#[from] ParseError),
 
+    #[error("JSON error: {0}")]
+    JsonError(#[from] JsonError),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] IoError),
+
+    #[error("Upload error: {0}")]
+    UploadError(String),
+
+    #[error("Message has no chat id")]
+    MissingChatId,
+
+    #[error("Middleware error: {0}")]
+    Middleware(BoxError),
+
+    #[error("Timed out waiting for an update")]
+    Timeout,
+
+    #[error("Waiting for an update was cancelled")]
+    WaitCancelled,
+
+    #[error("A reply from this user in this chat is already awaited")]
+    AlreadyWaiting,
+
+    #[error("Update handler panicked: {0}")]
+    HandlerPanic(String),
+
     #[error("Channel send error: {0}")]
-    SendError(#[from] SendError<Update>),
+    SendError(Box<SendError<Update>>),
+}
+
+impl From<SendError<Update>> for ApiClientError {
+    fn from(error: SendError<Update>) -> Self {
+        Self::SendError(Box::new(error))
+    }
+}
+
+impl ApiClientError {
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::ApiError { status, .. } => StatusCode::from_u16(*status).ok(),
+            Self::RequestError(e) => e.status(),
+            _ => None,
+        }
+    }
+
+    // Сетевые сбои, ошибки сервера и превышение лимита запросов
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::RequestError(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            // Тайм-ауты и перегрузка из слоёв tower, установленных через билдер
+            Self::Middleware(e) => {
+                e.is::<Elapsed>() || e.is::<tokio::time::error::Elapsed>() || e.is::<Overloaded>()
+            }
+            _ => self.status().is_some_and(is_transient_status),
+        }
+    }
+
+    pub fn is_attachment_not_ready(&self) -> bool {
+        matches!(self, Self::ApiError { code, .. } if code == "attachment.not.ready")
+    }
 }
@@ -0,0 +1,16 @@
+use std::collections::HashMap;
+
+use reqwest::Method;
+
+use crate::api::{ApiClient, models::CallbackAnswer, types::Result};
+
+impl ApiClient {
+    pub async fn answer_callback(&self, callback_id: &str, answer: &CallbackAnswer) -> Result<()> {
+        let mut query = HashMap::new();
+        query.insert("callback_id", callback_id.to_string());
+
+        self.send_request(Method::POST, "/answers", Some(&query), Some(answer))
+            .await?;
+        Ok(())
+    }
+}
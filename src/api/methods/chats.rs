@@ -4,7 +4,10 @@ use reqwest::Method;
 
 use crate::api::{
     ApiClient,
-    models::{Chat, ChatList, ChatPatch},
+    models::{
+        ActionRequestBody, Chat, ChatList, ChatMember, ChatMembersList, ChatPatch,
+        GetPinnedMessageResult, Message, PinMessageBody, SenderAction, UserIdsList,
+    },
     types::Result,
 };
 
@@ -54,4 +57,117 @@ impl ApiClient {
             .await?;
         Ok(())
     }
+
+    pub async fn get_chat_members(
+        &self,
+        chat_id: i64,
+        user_ids: Option<Vec<i64>>,
+        marker: Option<i64>,
+        count: Option<i32>,
+    ) -> Result<ChatMembersList> {
+        let mut query = HashMap::new();
+        if let Some(ids) = user_ids {
+            let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+            query.insert("user_ids", ids.join(","));
+        }
+        if let Some(m) = marker {
+            query.insert("marker", m.to_string());
+        }
+        if let Some(c) = count {
+            query.insert("count", c.to_string());
+        }
+
+        let path = format!("/chats/{}/members", chat_id);
+        let response = self
+            .send_request::<()>(Method::GET, &path, Some(&query), None)
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn add_chat_members(&self, chat_id: i64, user_ids: Vec<i64>) -> Result<()> {
+        let path = format!("/chats/{}/members", chat_id);
+        let body = UserIdsList { user_ids };
+
+        self.send_request(Method::POST, &path, None, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn remove_chat_member(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        block: Option<bool>,
+    ) -> Result<()> {
+        let mut query = HashMap::new();
+        query.insert("user_id", user_id.to_string());
+        if let Some(b) = block {
+            query.insert("block", b.to_string());
+        }
+
+        let path = format!("/chats/{}/members", chat_id);
+        self.send_request::<()>(Method::DELETE, &path, Some(&query), None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_chat_admins(&self, chat_id: i64) -> Result<ChatMembersList> {
+        let path = format!("/chats/{}/members/admins", chat_id);
+        let response = self
+            .send_request::<()>(Method::GET, &path, None, None)
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn get_my_membership(&self, chat_id: i64) -> Result<ChatMember> {
+        let path = format!("/chats/{}/members/me", chat_id);
+        let response = self
+            .send_request::<()>(Method::GET, &path, None, None)
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn leave_chat(&self, chat_id: i64) -> Result<()> {
+        let path = format!("/chats/{}/members/me", chat_id);
+        self.send_request::<()>(Method::DELETE, &path, None, None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_pinned_message(&self, chat_id: i64) -> Result<Option<Message>> {
+        let path = format!("/chats/{}/pin", chat_id);
+        let response = self
+            .send_request::<()>(Method::GET, &path, None, None)
+            .await?;
+        let result: GetPinnedMessageResult = response.json().await?;
+        Ok(result.message)
+    }
+
+    pub async fn pin_message(&self, chat_id: i64, message_id: &str, notify: bool) -> Result<()> {
+        let path = format!("/chats/{}/pin", chat_id);
+        let body = PinMessageBody {
+            message_id: message_id.to_string(),
+            notify: Some(notify),
+        };
+
+        self.send_request(Method::PUT, &path, None, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn unpin_message(&self, chat_id: i64) -> Result<()> {
+        let path = format!("/chats/{}/pin", chat_id);
+        self.send_request::<()>(Method::DELETE, &path, None, None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn send_action(&self, chat_id: i64, action: SenderAction) -> Result<()> {
+        let path = format!("/chats/{}/actions", chat_id);
+        let body = ActionRequestBody { action };
+
+        self.send_request(Method::POST, &path, None, Some(&body))
+            .await?;
+        Ok(())
+    }
 }
@@ -1,10 +1,11 @@
 use std::collections::HashMap;
 
 use reqwest::Method;
+use tokio::time::{Instant, sleep};
 
 use crate::api::{
     ApiClient,
-    models::{Message, NewMessageBody, SendMessageResult},
+    models::{Message, MessageList, NewMessageBody, SendMessageResult},
     types::Result,
 };
 
@@ -23,8 +24,64 @@ impl ApiClient {
             query.insert("user_id", uid.to_string());
         }
 
+        // Сообщение по user_id без chat_id расходует только глобальный бюджет ограничителя
+        let has_attachments = message
+            .attachments
+            .as_ref()
+            .is_some_and(|attachments| !attachments.is_empty());
+        let deadline = Instant::now() + self.attachment_wait.deadline;
+        let mut delay = self.attachment_wait.initial_delay;
+
+        loop {
+            self.acquire_chat(chat_id).await;
+
+            let result = self
+                .send_request(Method::POST, "/messages", Some(&query), Some(message))
+                .await;
+
+            match result {
+                Ok(response) => return Ok(response.json().await?),
+                // Загруженные вложения могут ещё обрабатываться на сервере
+                Err(e)
+                    if has_attachments
+                        && e.is_attachment_not_ready()
+                        && Instant::now() + delay < deadline =>
+                {
+                    sleep(delay).await;
+                    delay = (delay * 2).min(self.attachment_wait.max_delay);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    pub async fn get_messages(
+        &self,
+        chat_id: Option<i64>,
+        message_ids: Option<Vec<String>>,
+        from: Option<i64>,
+        to: Option<i64>,
+        count: Option<i32>,
+    ) -> Result<MessageList> {
+        let mut query = HashMap::new();
+        if let Some(cid) = chat_id {
+            query.insert("chat_id", cid.to_string());
+        }
+        if let Some(ids) = message_ids {
+            query.insert("message_ids", ids.join(","));
+        }
+        if let Some(f) = from {
+            query.insert("from", f.to_string());
+        }
+        if let Some(t) = to {
+            query.insert("to", t.to_string());
+        }
+        if let Some(c) = count {
+            query.insert("count", c.to_string());
+        }
+
         let response = self
-            .send_request(Method::POST, "/messages", Some(&query), Some(message))
+            .send_request::<()>(Method::GET, "/messages", Some(&query), None)
             .await?;
         Ok(response.json().await?)
     }
@@ -37,10 +94,33 @@ impl ApiClient {
         Ok(response.json().await?)
     }
 
+    // Чат по идентификатору сообщения неизвестен, поэтому правка расходует только глобальный
+    // бюджет ограничителя; для учёта бюджета чата используйте edit_message_in_chat
     pub async fn edit_message(&self, message_id: &str, message: &NewMessageBody) -> Result<()> {
+        self.edit_message_with_chat(None, message_id, message).await
+    }
+
+    // Зная чат, можно учесть правку в его бюджете ограничителя запросов
+    pub async fn edit_message_in_chat(
+        &self,
+        chat_id: i64,
+        message_id: &str,
+        message: &NewMessageBody,
+    ) -> Result<()> {
+        self.edit_message_with_chat(Some(chat_id), message_id, message)
+            .await
+    }
+
+    async fn edit_message_with_chat(
+        &self,
+        chat_id: Option<i64>,
+        message_id: &str,
+        message: &NewMessageBody,
+    ) -> Result<()> {
         let mut query = HashMap::new();
         query.insert("message_id", message_id.to_string());
 
+        self.acquire_chat(chat_id).await;
         self.send_request(Method::PUT, "/messages", Some(&query), Some(message))
             .await?;
         Ok(())
@@ -1,5 +1,8 @@
+mod answers;
 mod bots;
 mod chats;
 mod messages;
 mod subscriptions;
+mod updates;
 mod upload;
+mod videos;
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use reqwest::Method;
+
+use crate::api::{
+    ApiClient,
+    models::{RawUpdateList, UpdateList},
+    types::Result,
+};
+
+impl ApiClient {
+    pub async fn get_updates(
+        &self,
+        limit: Option<i32>,
+        timeout: Option<i32>,
+        marker: Option<i64>,
+        types: Option<Vec<String>>,
+    ) -> Result<UpdateList> {
+        let query = updates_query(limit, timeout, marker, types);
+
+        let response = self
+            .send_request::<()>(Method::GET, "/updates", Some(&query), None)
+            .await?;
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn get_raw_updates(
+        &self,
+        limit: Option<i32>,
+        timeout: Option<i32>,
+        marker: Option<i64>,
+        types: Option<Vec<String>>,
+    ) -> Result<RawUpdateList> {
+        let query = updates_query(limit, timeout, marker, types);
+
+        let response = self
+            .send_request::<()>(Method::GET, "/updates", Some(&query), None)
+            .await?;
+
+        Ok(response.json().await?)
+    }
+}
+
+fn updates_query(
+    limit: Option<i32>,
+    timeout: Option<i32>,
+    marker: Option<i64>,
+    types: Option<Vec<String>>,
+) -> HashMap<&'static str, String> {
+    let mut query = HashMap::new();
+
+    if let Some(l) = limit {
+        query.insert("limit", l.to_string());
+    }
+    if let Some(t) = timeout {
+        query.insert("timeout", t.to_string());
+    }
+    if let Some(m) = marker {
+        query.insert("marker", m.to_string());
+    }
+    if let Some(t) = types {
+        query.insert("types", t.join(","));
+    }
+
+    query
+}
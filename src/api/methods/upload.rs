@@ -1,36 +1,365 @@
 use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::Path;
 
-use reqwest::Method;
+use futures::StreamExt;
+use reqwest::{
+    Body, Method, Response,
+    header::{CONTENT_DISPOSITION, CONTENT_RANGE, CONTENT_TYPE},
+    multipart::{Form, Part},
+};
+use tokio::{
+    fs::File,
+    io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt},
+    sync::watch,
+};
+use tokio_util::io::ReaderStream;
 
-use crate::api::{ApiClient, models::UpdateList, types::Result};
+use crate::api::{
+    ApiClient,
+    error::ApiClientError,
+    models::{
+        AttachmentRequest, AudioAttachmentRequest, ChunkedUpload, ChunkedUploadOptions,
+        FileAttachmentRequest, PhotoAttachmentRequest, PhotoAttachmentRequestPayload,
+        PhotoUploadResult, UploadEndpoint, UploadProgress, UploadType, UploadedInfo,
+        VideoAttachmentRequest,
+    },
+    types::Result,
+};
 
 impl ApiClient {
-    pub async fn get_updates(
-        &self,
-        limit: Option<i32>,
-        timeout: Option<i32>,
-        marker: Option<i64>,
-        types: Option<Vec<String>>,
-    ) -> Result<UpdateList> {
+    pub async fn get_upload_url(&self, upload_type: UploadType) -> Result<UploadEndpoint> {
         let mut query = HashMap::new();
+        query.insert("type", upload_type_param(upload_type).to_string());
+
+        let response = self
+            .send_request::<()>(Method::POST, "/uploads", Some(&query), None)
+            .await?;
+        Ok(response.json().await?)
+    }
 
-        if let Some(l) = limit {
-            query.insert("limit", l.to_string());
+    pub async fn upload_bytes(
+        &self,
+        upload_type: UploadType,
+        filename: &str,
+        data: Vec<u8>,
+    ) -> Result<AttachmentRequest> {
+        let endpoint = self.get_upload_url(upload_type).await?;
+        let part = Part::bytes(data).file_name(filename.to_string());
+
+        let response = self.upload_part(&endpoint, part).await?;
+        attachment_from_upload(upload_type, endpoint, response).await
+    }
+
+    pub async fn upload_file(
+        &self,
+        upload_type: UploadType,
+        path: impl AsRef<Path>,
+    ) -> Result<AttachmentRequest> {
+        let path = path.as_ref();
+        let file = File::open(path).await?;
+        let length = file.metadata().await?.len();
+
+        self.upload_reader(upload_type, &file_name(path), file, Some(length), None)
+            .await
+    }
+
+    pub async fn upload_reader<R>(
+        &self,
+        upload_type: UploadType,
+        filename: &str,
+        reader: R,
+        length: Option<u64>,
+        progress: Option<watch::Sender<UploadProgress>>,
+    ) -> Result<AttachmentRequest>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        let endpoint = self.get_upload_url(upload_type).await?;
+
+        let mut sent = 0;
+        let stream = ReaderStream::new(reader).inspect(move |chunk| {
+            if let (Ok(chunk), Some(progress)) = (chunk, &progress) {
+                sent += chunk.len() as u64;
+                progress.send_replace(UploadProgress {
+                    sent,
+                    total: length,
+                });
+            }
+        });
+
+        let body = Body::wrap_stream(stream);
+        let part = match length {
+            Some(length) => Part::stream_with_length(body, length),
+            None => Part::stream(body),
         }
-        if let Some(t) = timeout {
-            query.insert("timeout", t.to_string());
+        .file_name(filename.to_string());
+
+        let response = self.upload_part(&endpoint, part).await?;
+        attachment_from_upload(upload_type, endpoint, response).await
+    }
+
+    pub async fn start_chunked_upload(
+        &self,
+        upload_type: UploadType,
+        filename: &str,
+        total: u64,
+    ) -> Result<ChunkedUpload> {
+        if !matches!(upload_type, UploadType::Video | UploadType::File) {
+            return Err(ApiClientError::UploadError(
+                "chunked upload supports only video and file".to_string(),
+            ));
         }
-        if let Some(m) = marker {
-            query.insert("marker", m.to_string());
+        if total == 0 {
+            return Err(ApiClientError::UploadError(
+                "chunked upload requires a non-empty source".to_string(),
+            ));
         }
-        if let Some(t) = types {
-            query.insert("types", t.join(","));
+
+        let endpoint = self.get_upload_url(upload_type).await?;
+
+        Ok(ChunkedUpload {
+            upload_type,
+            endpoint,
+            filename: filename.to_string(),
+            total,
+            offset: 0,
+        })
+    }
+
+    pub async fn resume_chunked_upload<R>(
+        &self,
+        upload: &mut ChunkedUpload,
+        reader: &mut R,
+        options: &ChunkedUploadOptions,
+    ) -> Result<UploadedInfo>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        check_chunk_size(options)?;
+
+        let mut retries = 0;
+
+        while upload.offset < upload.total {
+            let start = upload.offset;
+            let end = (start + options.chunk_size).min(upload.total);
+
+            reader.seek(SeekFrom::Start(start)).await?;
+            let mut chunk = vec![0; (end - start) as usize];
+            reader.read_exact(&mut chunk).await?;
+
+            match self.upload_chunk(upload, start, chunk).await {
+                Ok(body) => {
+                    retries = 0;
+                    // Сервер подтверждает принятый диапазон, продолжаем с его конца
+                    let offset = acknowledged_offset(&body).unwrap_or(end);
+                    if offset <= start || offset > end {
+                        return Err(ApiClientError::UploadError(format!(
+                            "server acknowledged offset {} for range {}-{}",
+                            offset, start, end
+                        )));
+                    }
+                    upload.offset = offset;
+
+                    if upload.offset >= upload.total {
+                        return chunked_upload_info(upload, &body);
+                    }
+                }
+                // Отказы сервера вроде 4xx повторять бессмысленно
+                Err(e) if e.is_transient() && retries < options.max_retries => {
+                    retries += 1;
+                    tokio::time::sleep(options.retry_delay).await;
+                }
+                Err(e) => return Err(e),
+            }
         }
 
-        let response = self
-            .send_request::<()>(Method::GET, "/updates", Some(&query), None)
+        chunked_upload_info(upload, &[])
+    }
+
+    pub async fn upload_file_chunked(
+        &self,
+        upload_type: UploadType,
+        path: impl AsRef<Path>,
+        options: &ChunkedUploadOptions,
+    ) -> Result<UploadedInfo> {
+        check_chunk_size(options)?;
+
+        let path = path.as_ref();
+        let mut file = File::open(path).await?;
+        let total = file.metadata().await?.len();
+
+        let mut upload = self
+            .start_chunked_upload(upload_type, &file_name(path), total)
             .await?;
 
-        Ok(response.json().await?)
+        self.resume_chunked_upload(&mut upload, &mut file, options)
+            .await
+    }
+
+    async fn upload_part(&self, endpoint: &UploadEndpoint, part: Part) -> Result<Response> {
+        let form = Form::new().part("data", part);
+
+        let request = self
+            .http_client
+            .post(&endpoint.url)
+            .multipart(form)
+            .build()?;
+        let response = self.execute(request).await?;
+
+        check_upload_response(response).await
+    }
+
+    async fn upload_chunk(
+        &self,
+        upload: &ChunkedUpload,
+        start: u64,
+        chunk: Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        let end = start + chunk.len() as u64 - 1;
+
+        let request = self
+            .http_client
+            .post(&upload.endpoint.url)
+            .header(
+                CONTENT_RANGE,
+                format!("bytes {}-{}/{}", start, end, upload.total),
+            )
+            .header(CONTENT_DISPOSITION, content_disposition(&upload.filename))
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .body(chunk)
+            .build()?;
+        let response = self.execute(request).await?;
+
+        // Статус сохраняется в ошибке, чтобы отличать временные сбои от отказов
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ApiClientError::ApiError {
+                status: status.as_u16(),
+                code: status.as_str().to_string(),
+                message: body,
+            });
+        }
+
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+async fn check_upload_response(response: Response) -> Result<Response> {
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(ApiClientError::UploadError(format!("{}: {}", status, body)));
+    }
+
+    Ok(response)
+}
+
+fn upload_type_param(upload_type: UploadType) -> &'static str {
+    match upload_type {
+        UploadType::Image => "image",
+        UploadType::Video => "video",
+        UploadType::Audio => "audio",
+        UploadType::File => "file",
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "file".to_string())
+}
+
+// Видео и аудио получают токен при запросе URL, фото и файлы — в ответе на загрузку
+async fn attachment_from_upload(
+    upload_type: UploadType,
+    endpoint: UploadEndpoint,
+    response: Response,
+) -> Result<AttachmentRequest> {
+    let attachment = match upload_type {
+        UploadType::Image => {
+            let result: PhotoUploadResult = response.json().await?;
+            AttachmentRequest::Image(PhotoAttachmentRequest {
+                payload: PhotoAttachmentRequestPayload {
+                    url: None,
+                    token: None,
+                    photos: Some(result.photos),
+                },
+            })
+        }
+        UploadType::Video => AttachmentRequest::Video(VideoAttachmentRequest {
+            payload: endpoint_token(endpoint)?,
+        }),
+        UploadType::Audio => AttachmentRequest::Audio(AudioAttachmentRequest {
+            payload: endpoint_token(endpoint)?,
+        }),
+        UploadType::File => {
+            let info: UploadedInfo = response.json().await?;
+            AttachmentRequest::File(FileAttachmentRequest { payload: info })
+        }
+    };
+
+    Ok(attachment)
+}
+
+fn endpoint_token(endpoint: UploadEndpoint) -> Result<UploadedInfo> {
+    endpoint
+        .token
+        .map(|token| UploadedInfo { token })
+        .ok_or_else(|| ApiClientError::UploadError("upload endpoint has no token".to_string()))
+}
+
+fn check_chunk_size(options: &ChunkedUploadOptions) -> Result<()> {
+    if options.chunk_size == 0 {
+        return Err(ApiClientError::UploadError(
+            "chunk size must be greater than zero".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// ASCII-вариант имени для старых серверов и полное имя в UTF-8 по RFC 6266
+fn content_disposition(filename: &str) -> String {
+    let mut fallback = String::new();
+    for c in filename.chars() {
+        match c {
+            '"' | '\\' => {
+                fallback.push('\\');
+                fallback.push(c);
+            }
+            c if c.is_ascii() && !c.is_ascii_control() => fallback.push(c),
+            _ => fallback.push('_'),
+        }
+    }
+
+    let mut encoded = String::new();
+    for byte in filename.bytes() {
+        if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        fallback, encoded
+    )
+}
+
+fn acknowledged_offset(body: &[u8]) -> Option<u64> {
+    let text = std::str::from_utf8(body).ok()?.trim();
+    let (range, _) = text.split_once('/')?;
+    let (_, end) = range.split_once('-')?;
+
+    end.parse::<u64>().ok().map(|end| end + 1)
+}
+
+fn chunked_upload_info(upload: &ChunkedUpload, body: &[u8]) -> Result<UploadedInfo> {
+    match upload.upload_type {
+        UploadType::File => Ok(serde_json::from_slice(body)?),
+        _ => endpoint_token(upload.endpoint.clone()),
     }
 }
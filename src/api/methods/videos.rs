@@ -0,0 +1,13 @@
+use reqwest::Method;
+
+use crate::api::{ApiClient, models::VideoAttachmentDetails, types::Result};
+
+impl ApiClient {
+    pub async fn get_video_details(&self, video_token: &str) -> Result<VideoAttachmentDetails> {
+        let path = format!("/videos/{}", video_token);
+        let response = self
+            .send_request::<()>(Method::GET, &path, None, None)
+            .await?;
+        Ok(response.json().await?)
+    }
+}
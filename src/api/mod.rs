@@ -3,6 +3,9 @@ mod client;
 pub mod error;
 pub mod methods;
 pub mod models;
+pub mod rate_limit;
+pub mod retry;
+pub mod service;
 pub mod types;
 
 pub use client::ApiClient;
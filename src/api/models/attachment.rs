@@ -3,12 +3,12 @@ use std::collections::HashMap;
 
 use super::{Button, ReplyButton};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Image {
     pub url: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Attachment {
     Image(PhotoAttachment),
@@ -24,19 +24,19 @@ pub enum Attachment {
     Data(DataAttachment),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhotoAttachment {
     pub payload: PhotoAttachmentPayload,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhotoAttachmentPayload {
     pub photo_id: i64,
     pub token: String,
     pub url: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoAttachment {
     pub payload: MediaAttachmentPayload,
     pub thumbnail: Option<VideoThumbnail>,
@@ -45,65 +45,128 @@ pub struct VideoAttachment {
     pub duration: Option<i32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaAttachmentPayload {
     pub url: String,
     pub token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoThumbnail {
     pub url: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoAttachmentDetails {
+    pub token: String,
+    pub urls: Option<VideoUrls>,
+    pub thumbnail: Option<PhotoAttachmentPayload>,
+    pub width: i32,
+    pub height: i32,
+    pub duration: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoUrls {
+    pub mp4_1080: Option<String>,
+    pub mp4_720: Option<String>,
+    pub mp4_480: Option<String>,
+    pub mp4_360: Option<String>,
+    pub mp4_240: Option<String>,
+    pub mp4_144: Option<String>,
+    pub hls: Option<String>,
+}
+
+impl VideoUrls {
+    pub fn mp4(&self, resolution: VideoResolution) -> Option<&str> {
+        let url = match resolution {
+            VideoResolution::P1080 => &self.mp4_1080,
+            VideoResolution::P720 => &self.mp4_720,
+            VideoResolution::P480 => &self.mp4_480,
+            VideoResolution::P360 => &self.mp4_360,
+            VideoResolution::P240 => &self.mp4_240,
+            VideoResolution::P144 => &self.mp4_144,
+        };
+        url.as_deref()
+    }
+
+    pub fn best_mp4(&self) -> Option<(VideoResolution, &str)> {
+        VideoResolution::ALL
+            .into_iter()
+            .find_map(|resolution| self.mp4(resolution).map(|url| (resolution, url)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VideoResolution {
+    P144,
+    P240,
+    P360,
+    P480,
+    P720,
+    P1080,
+}
+
+impl VideoResolution {
+    // От лучшего качества к худшему
+    pub const ALL: [VideoResolution; 6] = [
+        VideoResolution::P1080,
+        VideoResolution::P720,
+        VideoResolution::P480,
+        VideoResolution::P360,
+        VideoResolution::P240,
+        VideoResolution::P144,
+    ];
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileAttachment {
     pub payload: FileAttachmentPayload,
     pub filename: String,
     pub size: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileAttachmentPayload {
     pub url: String,
     pub token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StickerAttachment {
     pub payload: StickerAttachmentPayload,
     pub width: i32,
     pub height: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StickerAttachmentPayload {
     pub url: String,
     pub code: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContactAttachment {
     pub payload: ContactAttachmentPayload,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContactAttachmentPayload {
     pub vcf_info: Option<String>,
     pub max_info: Option<super::user::User>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InlineKeyboardAttachment {
     pub payload: super::keyboard::Keyboard,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReplyKeyboardAttachment {
     pub buttons: Vec<Vec<ReplyButton>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShareAttachment {
     pub payload: ShareAttachmentPayload,
     pub title: Option<String>,
@@ -111,24 +174,24 @@ pub struct ShareAttachment {
     pub image_url: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShareAttachmentPayload {
     pub url: Option<String>,
     pub token: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocationAttachment {
     pub latitude: f64,
     pub longitude: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataAttachment {
     pub data: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum AttachmentRequest {
     Image(PhotoAttachmentRequest),
@@ -143,54 +206,54 @@ pub enum AttachmentRequest {
     Share(ShareAttachmentRequest),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhotoAttachmentRequest {
     pub payload: PhotoAttachmentRequestPayload,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhotoAttachmentRequestPayload {
     pub url: Option<String>,
     pub token: Option<String>,
     pub photos: Option<HashMap<String, PhotoToken>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhotoToken {
     pub token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoAttachmentRequest {
     pub payload: UploadedInfo,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioAttachmentRequest {
     pub payload: UploadedInfo,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileAttachmentRequest {
     pub payload: UploadedInfo,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StickerAttachmentRequest {
     pub payload: StickerAttachmentRequestPayload,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StickerAttachmentRequestPayload {
     pub code: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContactAttachmentRequest {
     pub payload: ContactAttachmentRequestPayload,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContactAttachmentRequestPayload {
     pub name: Option<String>,
     pub contact_id: Option<i64>,
@@ -198,46 +261,51 @@ pub struct ContactAttachmentRequestPayload {
     pub vcf_phone: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InlineKeyboardAttachmentRequest {
     pub payload: InlineKeyboardAttachmentRequestPayload,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InlineKeyboardAttachmentRequestPayload {
     pub buttons: Vec<Vec<Button>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReplyKeyboardAttachmentRequest {
     pub direct: Option<bool>,
     pub direct_user_id: Option<i64>,
     pub buttons: Vec<Vec<ReplyButton>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocationAttachmentRequest {
     pub latitude: f64,
     pub longitude: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShareAttachmentRequest {
     pub payload: ShareAttachmentPayload,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhotoUploadResult {
+    pub photos: HashMap<String, PhotoToken>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadedInfo {
     pub token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadEndpoint {
     pub url: String,
     pub token: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum UploadType {
     Image,
@@ -246,13 +314,13 @@ pub enum UploadType {
     File,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioAttachment {
     pub payload: MediaAttachmentPayload,
     pub transcription: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum MarkupElement {
     Strong(StrongMarkup),
@@ -266,44 +334,44 @@ pub enum MarkupElement {
     Highlighted(HighlightedMarkup),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StrongMarkup {
     pub from: i32,
     pub length: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmphasizedMarkup {
     pub from: i32,
     pub length: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonospacedMarkup {
     pub from: i32,
     pub length: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinkMarkup {
     pub from: i32,
     pub length: i32,
     pub url: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StrikethroughMarkup {
     pub from: i32,
     pub length: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnderlineMarkup {
     pub from: i32,
     pub length: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserMentionMarkup {
     pub from: i32,
     pub length: i32,
@@ -311,13 +379,13 @@ pub struct UserMentionMarkup {
     pub user_id: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HeadingMarkup {
     pub from: i32,
     pub length: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HighlightedMarkup {
     pub from: i32,
     pub length: i32,
@@ -1,7 +1,7 @@
 use super::attachment::PhotoAttachmentRequestPayload;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotInfo {
     pub user_id: i64,
     pub first_name: String,
@@ -15,15 +15,19 @@ pub struct BotInfo {
     pub commands: Option<Vec<BotCommand>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub commands: Option<Vec<BotCommand>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub photo: Option<PhotoAttachmentRequestPayload>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BotCommand {
     pub name: String,
     pub description: Option<String>,
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Button {
     Callback(CallbackButton),
@@ -10,31 +10,31 @@ pub enum Button {
     Chat(ChatButton),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallbackButton {
     pub text: String,
     pub payload: String,
     pub intent: Option<Intent>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinkButton {
     pub text: String,
     pub url: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequestGeoLocationButton {
     pub text: String,
     pub quick: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequestContactButton {
     pub text: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatButton {
     pub text: String,
     pub chat_title: String,
@@ -43,7 +43,7 @@ pub struct ChatButton {
     pub uuid: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Intent {
     Positive,
@@ -51,7 +51,7 @@ pub enum Intent {
     Default,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum ReplyButton {
     Message(SendMessageButton),
@@ -59,21 +59,21 @@ pub enum ReplyButton {
     UserContact(SendContactButton),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendMessageButton {
     pub text: String,
     pub payload: Option<String>,
     pub intent: Option<Intent>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendGeoLocationButton {
     pub text: String,
     pub payload: Option<String>,
     pub quick: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendContactButton {
     pub text: String,
     pub payload: Option<String>,
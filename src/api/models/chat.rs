@@ -4,7 +4,7 @@ use super::user::UserWithPhoto;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Chat {
     pub chat_id: i64,
     #[serde(rename = "type")]
@@ -25,7 +25,7 @@ pub struct Chat {
     pub pinned_message: Option<Message>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ChatType {
     Dialog,
@@ -33,7 +33,7 @@ pub enum ChatType {
     Channel,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ChatStatus {
     Active,
@@ -43,16 +43,90 @@ pub enum ChatStatus {
     Suspended,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatList {
     pub chats: Vec<Chat>,
     pub marker: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatPatch {
     pub icon: Option<PhotoAttachmentRequestPayload>,
     pub title: Option<String>,
     pub pin: Option<String>,
     pub notify: Option<bool>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMember {
+    #[serde(flatten)]
+    pub user: UserWithPhoto,
+    pub last_access_time: Option<i64>,
+    pub is_owner: bool,
+    pub is_admin: bool,
+    pub join_time: i64,
+    pub permissions: Option<Vec<ChatAdminPermission>>,
+}
+
+impl ChatMember {
+    pub fn has_permission(&self, permission: ChatAdminPermission) -> bool {
+        self.is_owner
+            || self
+                .permissions
+                .as_ref()
+                .is_some_and(|permissions| permissions.contains(&permission))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatAdminPermission {
+    ReadAllMessages,
+    AddRemoveMembers,
+    AddAdmins,
+    ChangeChatInfo,
+    PinMessage,
+    Write,
+    EditLink,
+    DeleteMessages,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMembersList {
+    pub members: Vec<ChatMember>,
+    pub marker: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserIdsList {
+    pub user_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPinnedMessageResult {
+    pub message: Option<Message>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinMessageBody {
+    pub message_id: String,
+    pub notify: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SenderAction {
+    TypingOn,
+    SendingPhoto,
+    SendingVideo,
+    SendingAudio,
+    SendingFile,
+    MarkSeen,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionRequestBody {
+    pub action: SenderAction,
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use super::Button;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Keyboard {
     pub buttons: Vec<Vec<Button>>,
 }
@@ -1,8 +1,8 @@
-use super::attachment::{Attachment, MarkupElement};
+use super::attachment::{Attachment, ContactAttachment, LocationAttachment, MarkupElement};
 use super::user::User;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub sender: Option<User>,
     pub recipient: Recipient,
@@ -13,14 +13,38 @@ pub struct Message {
     pub url: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Message {
+    pub fn attachments(&self) -> &[Attachment] {
+        self.body.attachments.as_deref().unwrap_or_default()
+    }
+
+    pub fn contact(&self) -> Option<&ContactAttachment> {
+        self.attachments()
+            .iter()
+            .find_map(|attachment| match attachment {
+                Attachment::Contact(contact) => Some(contact),
+                _ => None,
+            })
+    }
+
+    pub fn location(&self) -> Option<&LocationAttachment> {
+        self.attachments()
+            .iter()
+            .find_map(|attachment| match attachment {
+                Attachment::Location(location) => Some(location),
+                _ => None,
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Recipient {
     pub chat_id: Option<i64>,
     pub chat_type: super::chat::ChatType,
     pub user_id: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinkedMessage {
     #[serde(rename = "type")]
     pub link_type: MessageLinkType,
@@ -29,14 +53,14 @@ pub struct LinkedMessage {
     pub message: MessageBody,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum MessageLinkType {
     Forward,
     Reply,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageBody {
     pub mid: String,
     pub seq: i64,
@@ -45,17 +69,17 @@ pub struct MessageBody {
     pub markup: Option<Vec<MarkupElement>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageStat {
     pub views: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageList {
     pub messages: Vec<Message>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewMessageBody {
     pub text: Option<String>,
     pub attachments: Option<Vec<super::attachment::AttachmentRequest>>,
@@ -64,19 +88,19 @@ pub struct NewMessageBody {
     pub format: Option<TextFormat>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TextFormat {
     Markdown,
     Html,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendMessageResult {
     pub message: Message,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewMessageLink {
     #[serde(rename = "type")]
     pub link_type: MessageLinkType,
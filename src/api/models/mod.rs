@@ -6,6 +6,7 @@ mod error;
 mod keyboard;
 mod message;
 mod update;
+mod upload;
 mod user;
 
 pub use attachment::*;
@@ -16,4 +17,5 @@ pub use error::*;
 pub use keyboard::*;
 pub use message::*;
 pub use update::*;
+pub use upload::*;
 pub use user::*;
@@ -1,9 +1,9 @@
-use super::chat::Chat;
-use super::message::Message;
+use super::chat::{Chat, ChatType};
+use super::message::{Message, NewMessageBody};
 use super::user::User;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Callback {
     pub timestamp: i64,
     pub callback_id: String,
@@ -11,12 +11,18 @@ pub struct Callback {
     pub user: User,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallbackAnswer {
+    pub message: Option<NewMessageBody>,
+    pub notification: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetSubscriptionsResult {
     pub subscriptions: Vec<Subscription>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Subscription {
     pub url: String,
     pub time: i64,
@@ -24,7 +30,7 @@ pub struct Subscription {
     pub version: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubscriptionRequestBody {
     pub url: String,
     pub secret: Option<String>,
@@ -32,13 +38,21 @@ pub struct SubscriptionRequestBody {
     pub version: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateList {
     pub updates: Vec<Update>,
     pub marker: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+// Обновления без разбора, чтобы одно некорректное не ломало всю пачку
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawUpdateList {
+    pub updates: Vec<serde_json::Value>,
+    pub marker: Option<i64>,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "update_type")]
 #[serde(rename_all = "snake_case")]
 pub enum Update {
@@ -55,14 +69,109 @@ pub enum Update {
     MessageChatCreated(MessageChatCreatedUpdate),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateKind {
+    MessageCreated,
+    MessageCallback,
+    MessageEdited,
+    MessageRemoved,
+    BotAdded,
+    BotRemoved,
+    UserAdded,
+    UserRemoved,
+    BotStarted,
+    ChatTitleChanged,
+    MessageChatCreated,
+}
+
+impl Update {
+    pub fn kind(&self) -> UpdateKind {
+        match self {
+            Update::MessageCreated(_) => UpdateKind::MessageCreated,
+            Update::MessageCallback(_) => UpdateKind::MessageCallback,
+            Update::MessageEdited(_) => UpdateKind::MessageEdited,
+            Update::MessageRemoved(_) => UpdateKind::MessageRemoved,
+            Update::BotAdded(_) => UpdateKind::BotAdded,
+            Update::BotRemoved(_) => UpdateKind::BotRemoved,
+            Update::UserAdded(_) => UpdateKind::UserAdded,
+            Update::UserRemoved(_) => UpdateKind::UserRemoved,
+            Update::BotStarted(_) => UpdateKind::BotStarted,
+            Update::ChatTitleChanged(_) => UpdateKind::ChatTitleChanged,
+            Update::MessageChatCreated(_) => UpdateKind::MessageChatCreated,
+        }
+    }
+
+    pub fn message(&self) -> Option<&Message> {
+        match self {
+            Update::MessageCreated(u) => Some(&u.message),
+            Update::MessageCallback(u) => u.message.as_ref(),
+            Update::MessageEdited(u) => Some(&u.message),
+            _ => None,
+        }
+    }
+
+    pub fn chat_id(&self) -> Option<i64> {
+        match self {
+            Update::MessageRemoved(u) => Some(u.chat_id),
+            Update::BotAdded(u) => Some(u.chat_id),
+            Update::BotRemoved(u) => Some(u.chat_id),
+            Update::UserAdded(u) => Some(u.chat_id),
+            Update::UserRemoved(u) => Some(u.chat_id),
+            Update::BotStarted(u) => Some(u.chat_id),
+            Update::ChatTitleChanged(u) => Some(u.chat_id),
+            Update::MessageChatCreated(u) => Some(u.chat.chat_id),
+            _ => self.message().and_then(|m| m.recipient.chat_id),
+        }
+    }
+
+    pub fn chat_type(&self) -> Option<ChatType> {
+        match self {
+            Update::MessageChatCreated(u) => Some(u.chat.chat_type),
+            _ => self.message().map(|m| m.recipient.chat_type),
+        }
+    }
+
+    pub fn user(&self) -> Option<&User> {
+        match self {
+            Update::MessageCreated(u) => u.message.sender.as_ref(),
+            Update::MessageCallback(u) => Some(&u.callback.user),
+            Update::MessageEdited(u) => u.message.sender.as_ref(),
+            Update::BotAdded(u) => Some(&u.user),
+            Update::BotRemoved(u) => Some(&u.user),
+            Update::UserAdded(u) => Some(&u.user),
+            Update::UserRemoved(u) => Some(&u.user),
+            Update::BotStarted(u) => Some(&u.user),
+            Update::ChatTitleChanged(u) => Some(&u.user),
+            Update::MessageRemoved(_) | Update::MessageChatCreated(_) => None,
+        }
+    }
+
+    pub fn user_id(&self) -> Option<i64> {
+        match self {
+            Update::MessageRemoved(u) => Some(u.user_id),
+            _ => self.user().map(|user| user.user_id),
+        }
+    }
+
+    pub fn user_locale(&self) -> Option<&str> {
+        match self {
+            Update::MessageCreated(u) => u.user_locale.as_deref(),
+            Update::MessageCallback(u) => u.user_locale.as_deref(),
+            Update::BotStarted(u) => u.user_locale.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageCreatedUpdate {
     pub timestamp: i64,
     pub message: Message,
     pub user_locale: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageCallbackUpdate {
     pub timestamp: i64,
     pub callback: Callback,
@@ -70,13 +179,13 @@ pub struct MessageCallbackUpdate {
     pub user_locale: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageEditedUpdate {
     pub timestamp: i64,
     pub message: Message,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageRemovedUpdate {
     pub timestamp: i64,
     pub message_id: String,
@@ -84,7 +193,7 @@ pub struct MessageRemovedUpdate {
     pub user_id: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotAddedToChatUpdate {
     pub timestamp: i64,
     pub chat_id: i64,
@@ -92,7 +201,7 @@ pub struct BotAddedToChatUpdate {
     pub is_channel: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotRemovedFromChatUpdate {
     pub timestamp: i64,
     pub chat_id: i64,
@@ -100,7 +209,7 @@ pub struct BotRemovedFromChatUpdate {
     pub is_channel: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserAddedToChatUpdate {
     pub timestamp: i64,
     pub chat_id: i64,
@@ -109,7 +218,7 @@ pub struct UserAddedToChatUpdate {
     pub is_channel: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserRemovedFromChatUpdate {
     pub timestamp: i64,
     pub chat_id: i64,
@@ -118,7 +227,7 @@ pub struct UserRemovedFromChatUpdate {
     pub is_channel: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotStartedUpdate {
     pub timestamp: i64,
     pub chat_id: i64,
@@ -127,7 +236,7 @@ pub struct BotStartedUpdate {
     pub user_locale: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatTitleChangedUpdate {
     pub timestamp: i64,
     pub chat_id: i64,
@@ -135,7 +244,7 @@ pub struct ChatTitleChangedUpdate {
     pub title: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageChatCreatedUpdate {
     pub timestamp: i64,
     pub chat: Chat,
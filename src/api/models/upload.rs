@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::attachment::{UploadEndpoint, UploadType};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UploadProgress {
+    pub sent: u64,
+    pub total: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkedUpload {
+    pub upload_type: UploadType,
+    pub endpoint: UploadEndpoint,
+    pub filename: String,
+    pub total: u64,
+    pub offset: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChunkedUploadOptions {
+    pub chunk_size: u64,
+    pub max_retries: u32,
+    pub retry_delay: Duration,
+}
+
+impl Default for ChunkedUploadOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: 4 * 1024 * 1024,
+            max_retries: 5,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AttachmentWaitOptions {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub deadline: Duration,
+}
+
+impl Default for AttachmentWaitOptions {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(5),
+            deadline: Duration::from_secs(60),
+        }
+    }
+}
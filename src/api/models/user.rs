@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub user_id: i64,
     pub first_name: String,
@@ -10,7 +10,7 @@ pub struct User {
     pub last_activity_time: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserWithPhoto {
     pub user_id: i64,
     pub first_name: String,
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time::{Instant, sleep};
+
+// Неиспользуемые корзины чатов удаляются, когда их становится больше этого числа
+const CHAT_BUCKETS_PRUNE_THRESHOLD: usize = 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, period: Duration) -> Self {
+        Self { requests, period }
+    }
+
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    fn capacity(&self) -> f64 {
+        f64::from(self.requests.max(1))
+    }
+
+    fn tokens_per_second(&self) -> f64 {
+        self.capacity() / self.period.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitOptions {
+    pub global: RateLimit,
+    pub per_chat: Option<RateLimit>,
+}
+
+impl Default for RateLimitOptions {
+    fn default() -> Self {
+        Self {
+            global: RateLimit::per_second(30),
+            per_chat: Some(RateLimit::per_second(3)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    global: TokenBucket,
+    per_chat: Option<RateLimit>,
+    chats: StdMutex<HashMap<i64, Arc<TokenBucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(options: RateLimitOptions) -> Self {
+        Self {
+            global: TokenBucket::new(options.global),
+            per_chat: options.per_chat,
+            chats: StdMutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        self.global.acquire().await;
+    }
+
+    // Глобальный бюджет расходуется отдельно, при отправке самого запроса
+    pub async fn acquire_chat(&self, chat_id: i64) {
+        if let Some(bucket) = self.chat_bucket(chat_id) {
+            bucket.acquire().await;
+        }
+    }
+
+    fn chat_bucket(&self, chat_id: i64) -> Option<Arc<TokenBucket>> {
+        let limit = self.per_chat?;
+        let mut chats = self.chats.lock().unwrap();
+
+        if chats.len() >= CHAT_BUCKETS_PRUNE_THRESHOLD && !chats.contains_key(&chat_id) {
+            chats.retain(|_, bucket| Arc::strong_count(bucket) > 1 || !bucket.is_full());
+        }
+
+        let bucket = chats
+            .entry(chat_id)
+            .or_insert_with(|| Arc::new(TokenBucket::new(limit)));
+        Some(bucket.clone())
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens_per_second: f64,
+    // Асинхронный мьютекс выдаёт блокировку в порядке очереди, ожидающие обслуживаются честно
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        let capacity = limit.capacity();
+
+        Self {
+            capacity,
+            tokens_per_second: limit.tokens_per_second(),
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    async fn acquire(&self) {
+        let mut state = self.state.lock().await;
+        self.refill(&mut state);
+
+        if state.tokens < 1.0 {
+            let missing = 1.0 - state.tokens;
+            sleep(Duration::from_secs_f64(missing / self.tokens_per_second)).await;
+            self.refill(&mut state);
+        }
+
+        state.tokens -= 1.0;
+    }
+
+    fn is_full(&self) -> bool {
+        match self.state.try_lock() {
+            Ok(mut state) => {
+                self.refill(&mut state);
+                state.tokens >= self.capacity
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn refill(&self, state: &mut BucketState) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.updated).as_secs_f64();
+
+        state.tokens = (state.tokens + elapsed * self.tokens_per_second).min(self.capacity);
+        state.updated = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use tokio::time::{Instant, advance};
+
+    use super::{CHAT_BUCKETS_PRUNE_THRESHOLD, RateLimit, RateLimitOptions, RateLimiter};
+
+    fn limiter(global: u32, per_chat: Option<u32>) -> RateLimiter {
+        RateLimiter::new(RateLimitOptions {
+            global: RateLimit::per_second(global),
+            per_chat: per_chat.map(RateLimit::per_second),
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn global_budget_refills_over_time() {
+        let limiter = limiter(2, None);
+        let start = Instant::now();
+
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+
+        advance(Duration::from_secs(1)).await;
+        let start = Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn chat_budget_blocks_only_its_chat() {
+        let limiter = limiter(100, Some(1));
+        let start = Instant::now();
+
+        limiter.acquire_chat(1).await;
+        limiter.acquire_chat(2).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        limiter.acquire_chat(1).await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waiters_are_served_in_arrival_order() {
+        let limiter = Arc::new(limiter(1, None));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let tasks: Vec<_> = (0..5)
+            .map(|i| {
+                let limiter = limiter.clone();
+                let order = order.clone();
+                tokio::spawn(async move {
+                    limiter.acquire().await;
+                    order.lock().unwrap().push(i);
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(*order.lock().unwrap(), [0, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_chat_buckets_are_pruned() {
+        let limiter = limiter(u32::MAX, Some(1));
+
+        for chat_id in 0..CHAT_BUCKETS_PRUNE_THRESHOLD as i64 {
+            limiter.acquire_chat(chat_id).await;
+        }
+        assert_eq!(
+            limiter.chats.lock().unwrap().len(),
+            CHAT_BUCKETS_PRUNE_THRESHOLD
+        );
+
+        // Корзина чата 0 снова опустошена и должна пережить очистку
+        advance(Duration::from_secs(1)).await;
+        limiter.acquire_chat(0).await;
+        limiter.acquire_chat(-1).await;
+
+        let chats = limiter.chats.lock().unwrap();
+        assert_eq!(chats.len(), 2);
+        assert!(chats.contains_key(&0) && chats.contains_key(&-1));
+    }
+}
@@ -0,0 +1,55 @@
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use reqwest::{Client, Request, Response};
+use tower::{BoxError, Layer, Service, ServiceExt, util::BoxCloneSyncService};
+
+use super::error::ApiClientError;
+use crate::api::types::Result;
+
+pub type HttpService = BoxCloneSyncService<Request, Response, ApiClientError>;
+
+#[derive(Debug, Clone)]
+pub struct ReqwestService {
+    client: Client,
+}
+
+impl ReqwestService {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Service<Request> for ReqwestService {
+    type Response = Response;
+    type Error = ApiClientError;
+    type Future = BoxFuture<'static, Result<Response>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let client = self.client.clone();
+        Box::pin(async move { Ok(client.execute(request).await?) })
+    }
+}
+
+pub fn layer_service<L>(service: HttpService, layer: L) -> HttpService
+where
+    L: Layer<HttpService>,
+    L::Service: Service<Request, Response = Response> + Clone + Send + Sync + 'static,
+    <L::Service as Service<Request>>::Error: Into<BoxError>,
+    <L::Service as Service<Request>>::Future: Send + 'static,
+{
+    let service = layer.layer(service).map_err(|e| into_api_error(e.into()));
+    BoxCloneSyncService::new(service)
+}
+
+// Ошибки транспорта, обёрнутые слоями tower, возвращаются в исходном виде
+fn into_api_error(error: BoxError) -> ApiClientError {
+    match error.downcast::<ApiClientError>() {
+        Ok(error) => *error,
+        Err(error) => ApiClientError::Middleware(error),
+    }
+}
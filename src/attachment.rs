@@ -0,0 +1,13 @@
+use crate::{
+    api::{
+        models::{VideoAttachment, VideoAttachmentDetails},
+        types::Result,
+    },
+    client::MaxBotClient,
+};
+
+impl VideoAttachment {
+    pub async fn details(&self, client: &MaxBotClient) -> Result<VideoAttachmentDetails> {
+        client.get_video_details(&self.payload.token).await
+    }
+}
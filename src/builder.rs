@@ -0,0 +1,101 @@
+use reqwest::{Client, Request, Response};
+use tower::{BoxError, Layer, Service, util::BoxCloneSyncService};
+use url::Url;
+
+use crate::{
+    api::{
+        ApiClient,
+        models::AttachmentWaitOptions,
+        rate_limit::RateLimitOptions,
+        retry::RetryPolicy,
+        service::{HttpService, ReqwestService, layer_service},
+    },
+    client::MaxBotClient,
+    error::MaxBotError,
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://botapi.max.ru";
+
+type LayerFn = Box<dyn FnOnce(HttpService) -> HttpService + Send>;
+
+pub struct MaxBotClientBuilder {
+    access_token: String,
+    base_url: String,
+    http_client: Option<Client>,
+    layers: Vec<LayerFn>,
+    attachment_wait: AttachmentWaitOptions,
+    retry_policy: RetryPolicy,
+    rate_limit: Option<RateLimitOptions>,
+}
+
+impl MaxBotClientBuilder {
+    pub fn new(access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: access_token.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            http_client: None,
+            layers: Vec::new(),
+            attachment_wait: AttachmentWaitOptions::default(),
+            retry_policy: RetryPolicy::default(),
+            rate_limit: None,
+        }
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn http_client(mut self, http_client: Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    pub fn attachment_wait(mut self, options: AttachmentWaitOptions) -> Self {
+        self.attachment_wait = options;
+        self
+    }
+
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    pub fn rate_limit(mut self, options: RateLimitOptions) -> Self {
+        self.rate_limit = Some(options);
+        self
+    }
+
+    // Первый добавленный слой оказывается внешним, как в tower::ServiceBuilder
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<HttpService> + Send + 'static,
+        L::Service: Service<Request, Response = Response> + Clone + Send + Sync + 'static,
+        <L::Service as Service<Request>>::Error: Into<BoxError>,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        self.layers
+            .push(Box::new(move |service| layer_service(service, layer)));
+        self
+    }
+
+    pub fn build(self) -> Result<MaxBotClient, MaxBotError> {
+        let base_url =
+            Url::parse(&self.base_url).map_err(|e| MaxBotError::InvalidUrl(e.to_string()))?;
+        let http_client = self.http_client.unwrap_or_default();
+
+        let mut service: HttpService =
+            BoxCloneSyncService::new(ReqwestService::new(http_client.clone()));
+        for layer in self.layers.into_iter().rev() {
+            service = layer(service);
+        }
+
+        let mut api_client =
+            ApiClient::with_service(self.access_token, base_url, http_client, service);
+        api_client.set_attachment_wait(self.attachment_wait);
+        api_client.set_retry_policy(self.retry_policy);
+        api_client.set_rate_limit(self.rate_limit);
+
+        Ok(MaxBotClient { api_client })
+    }
+}
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::{api::models::SenderAction, client::MaxBotClient};
+
+// Клиент сбрасывает индикатор действия через несколько секунд
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[must_use = "действие перестаёт отправляться, когда guard удаляется"]
+pub struct ChatActionGuard {
+    task: JoinHandle<()>,
+}
+
+impl ChatActionGuard {
+    pub fn new(client: MaxBotClient, chat_id: i64, action: SenderAction) -> Self {
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+
+            loop {
+                interval.tick().await;
+                let _ = client.send_action(chat_id, action).await;
+            }
+        });
+
+        Self { task }
+    }
+}
+
+impl Drop for ChatActionGuard {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
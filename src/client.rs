@@ -1,12 +1,27 @@
-use url::Url;
+use std::path::Path;
+
+use futures::Stream;
+use tokio::{
+    io::{AsyncRead, AsyncSeek},
+    sync::watch,
+};
 
 use crate::api::ApiClient;
 use crate::api::models::{
-    BotInfo, BotPatch, Chat, ChatList, ChatPatch, GetSubscriptionsResult, Message, NewMessageBody,
-    SendMessageResult, SubscriptionRequestBody, UpdateList,
+    AttachmentRequest, AttachmentWaitOptions, BotInfo, BotPatch, CallbackAnswer, Chat, ChatList,
+    ChatMember, ChatMembersList, ChatPatch, ChunkedUpload, ChunkedUploadOptions,
+    GetSubscriptionsResult, Message, MessageList, NewMessageBody, RawUpdateList, SendMessageResult,
+    SenderAction, SubscriptionRequestBody, UpdateList, UploadEndpoint, UploadProgress, UploadType,
+    UploadedInfo, VideoAttachmentDetails,
 };
+use crate::api::rate_limit::RateLimitOptions;
+use crate::api::retry::RetryPolicy;
 use crate::api::types::Result;
+use crate::builder::{DEFAULT_BASE_URL, MaxBotClientBuilder};
+use crate::chat_action::ChatActionGuard;
+use crate::command::BotCommands;
 use crate::error::MaxBotError;
+use crate::history::message_history;
 
 #[derive(Debug, Clone)]
 pub struct MaxBotClient {
@@ -15,17 +30,36 @@ pub struct MaxBotClient {
 
 impl MaxBotClient {
     pub fn new(access_token: String) -> Self {
-        Self::with_base_url(access_token, "https://botapi.max.ru").unwrap()
+        Self::with_base_url(access_token, DEFAULT_BASE_URL).unwrap()
     }
 
     pub fn with_base_url(
         access_token: String,
         base_url: &str,
     ) -> std::result::Result<Self, MaxBotError> {
-        let base_url = Url::parse(base_url).map_err(|e| MaxBotError::InvalidUrl(e.to_string()))?;
-        let api_client = ApiClient::new(access_token.clone(), base_url.clone());
+        Self::builder(access_token).base_url(base_url).build()
+    }
+
+    pub fn builder(access_token: impl Into<String>) -> MaxBotClientBuilder {
+        MaxBotClientBuilder::new(access_token)
+    }
+
+    pub fn set_attachment_wait(&mut self, options: AttachmentWaitOptions) {
+        self.api_client.set_attachment_wait(options);
+    }
 
-        Ok(Self { api_client })
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.api_client.set_retry_policy(policy);
+    }
+
+    pub fn set_rate_limit(&mut self, options: Option<RateLimitOptions>) {
+        self.api_client.set_rate_limit(options);
+    }
+
+    pub fn with_retry_policy(&self, policy: RetryPolicy) -> Self {
+        Self {
+            api_client: self.api_client.with_retry_policy(policy),
+        }
     }
 
     // Bot methods
@@ -37,6 +71,26 @@ impl MaxBotClient {
         self.api_client.edit_my_info(patch).await
     }
 
+    // Возвращает true, если список команд бота был обновлён
+    pub async fn sync_commands<C: BotCommands>(&self) -> Result<bool> {
+        let commands = C::bot_commands();
+        let info = self.get_my_info().await?;
+
+        if info.commands.unwrap_or_default() == commands {
+            return Ok(false);
+        }
+
+        let patch = BotPatch {
+            name: None,
+            description: None,
+            commands: Some(commands),
+            photo: None,
+        };
+        self.edit_my_info(&patch).await?;
+
+        Ok(true)
+    }
+
     // Chat methods
     pub async fn get_chats(&self, count: Option<i32>, marker: Option<i64>) -> Result<ChatList> {
         self.api_client.get_chats(count, marker).await
@@ -58,6 +112,73 @@ impl MaxBotClient {
         self.api_client.delete_chat(chat_id).await
     }
 
+    pub async fn get_chat_members(
+        &self,
+        chat_id: i64,
+        user_ids: Option<Vec<i64>>,
+        marker: Option<i64>,
+        count: Option<i32>,
+    ) -> Result<ChatMembersList> {
+        self.api_client
+            .get_chat_members(chat_id, user_ids, marker, count)
+            .await
+    }
+
+    pub async fn add_chat_members(&self, chat_id: i64, user_ids: Vec<i64>) -> Result<()> {
+        self.api_client.add_chat_members(chat_id, user_ids).await
+    }
+
+    pub async fn remove_chat_member(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        block: Option<bool>,
+    ) -> Result<()> {
+        self.api_client
+            .remove_chat_member(chat_id, user_id, block)
+            .await
+    }
+
+    pub async fn get_chat_admins(&self, chat_id: i64) -> Result<ChatMembersList> {
+        self.api_client.get_chat_admins(chat_id).await
+    }
+
+    pub async fn get_my_membership(&self, chat_id: i64) -> Result<ChatMember> {
+        self.api_client.get_my_membership(chat_id).await
+    }
+
+    pub async fn leave_chat(&self, chat_id: i64) -> Result<()> {
+        self.api_client.leave_chat(chat_id).await
+    }
+
+    pub async fn get_pinned_message(&self, chat_id: i64) -> Result<Option<Message>> {
+        self.api_client.get_pinned_message(chat_id).await
+    }
+
+    pub async fn pin_message(&self, chat_id: i64, message_id: &str, notify: bool) -> Result<()> {
+        self.api_client
+            .pin_message(chat_id, message_id, notify)
+            .await
+    }
+
+    pub async fn unpin_message(&self, chat_id: i64) -> Result<()> {
+        self.api_client.unpin_message(chat_id).await
+    }
+
+    pub async fn send_action(&self, chat_id: i64, action: SenderAction) -> Result<()> {
+        self.api_client.send_action(chat_id, action).await
+    }
+
+    #[must_use = "действие отправляется, пока guard не удалён"]
+    pub fn keep_action(&self, chat_id: i64, action: SenderAction) -> ChatActionGuard {
+        ChatActionGuard::new(self.clone(), chat_id, action)
+    }
+
+    #[must_use = "действие отправляется, пока guard не удалён"]
+    pub fn typing(&self, chat_id: i64) -> ChatActionGuard {
+        self.keep_action(chat_id, SenderAction::TypingOn)
+    }
+
     // Message methods
     pub async fn send_message(
         &self,
@@ -69,18 +190,68 @@ impl MaxBotClient {
         self.api_client.send_message(&message, chat_id, None).await
     }
 
+    pub async fn send_message_with_attachments(
+        &self,
+        chat_id: Option<i64>,
+        text: impl Into<String>,
+        attachments: Vec<AttachmentRequest>,
+    ) -> Result<SendMessageResult> {
+        let mut message = NewMessageBody::new(text.into());
+        message.attachments = Some(attachments);
+
+        self.api_client.send_message(&message, chat_id, None).await
+    }
+
+    pub async fn get_messages(
+        &self,
+        chat_id: Option<i64>,
+        message_ids: Option<Vec<String>>,
+        from: Option<i64>,
+        to: Option<i64>,
+        count: Option<i32>,
+    ) -> Result<MessageList> {
+        self.api_client
+            .get_messages(chat_id, message_ids, from, to, count)
+            .await
+    }
+
+    pub fn message_history(
+        &self,
+        chat_id: i64,
+        page_size: i32,
+    ) -> impl Stream<Item = Result<Message>> + Send + use<> {
+        message_history(self.clone(), chat_id, page_size)
+    }
+
     pub async fn get_message(&self, message_id: &str) -> Result<Message> {
         self.api_client.get_message(message_id).await
     }
 
+    // Учитывает только глобальный бюджет ограничителя, см. edit_message_in_chat
     pub async fn edit_message(&self, message_id: &str, message: &NewMessageBody) -> Result<()> {
         self.api_client.edit_message(message_id, message).await
     }
 
+    pub async fn edit_message_in_chat(
+        &self,
+        chat_id: i64,
+        message_id: &str,
+        message: &NewMessageBody,
+    ) -> Result<()> {
+        self.api_client
+            .edit_message_in_chat(chat_id, message_id, message)
+            .await
+    }
+
     pub async fn delete_message(&self, message_id: &str) -> Result<()> {
         self.api_client.delete_message(message_id).await
     }
 
+    // Callback methods
+    pub async fn answer_callback(&self, callback_id: &str, answer: &CallbackAnswer) -> Result<()> {
+        self.api_client.answer_callback(callback_id, answer).await
+    }
+
     // Subscription methods
     pub async fn get_subscriptions(&self) -> Result<GetSubscriptionsResult> {
         self.api_client.get_subscriptions().await
@@ -94,6 +265,87 @@ impl MaxBotClient {
         self.api_client.unsubscribe(url).await
     }
 
+    // Upload methods
+    pub async fn get_upload_url(&self, upload_type: UploadType) -> Result<UploadEndpoint> {
+        self.api_client.get_upload_url(upload_type).await
+    }
+
+    pub async fn upload_bytes(
+        &self,
+        upload_type: UploadType,
+        filename: &str,
+        data: Vec<u8>,
+    ) -> Result<AttachmentRequest> {
+        self.api_client
+            .upload_bytes(upload_type, filename, data)
+            .await
+    }
+
+    pub async fn upload_file(
+        &self,
+        upload_type: UploadType,
+        path: impl AsRef<Path>,
+    ) -> Result<AttachmentRequest> {
+        self.api_client.upload_file(upload_type, path).await
+    }
+
+    pub async fn upload_reader<R>(
+        &self,
+        upload_type: UploadType,
+        filename: &str,
+        reader: R,
+        length: Option<u64>,
+        progress: Option<watch::Sender<UploadProgress>>,
+    ) -> Result<AttachmentRequest>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        self.api_client
+            .upload_reader(upload_type, filename, reader, length, progress)
+            .await
+    }
+
+    pub async fn start_chunked_upload(
+        &self,
+        upload_type: UploadType,
+        filename: &str,
+        total: u64,
+    ) -> Result<ChunkedUpload> {
+        self.api_client
+            .start_chunked_upload(upload_type, filename, total)
+            .await
+    }
+
+    pub async fn resume_chunked_upload<R>(
+        &self,
+        upload: &mut ChunkedUpload,
+        reader: &mut R,
+        options: &ChunkedUploadOptions,
+    ) -> Result<UploadedInfo>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        self.api_client
+            .resume_chunked_upload(upload, reader, options)
+            .await
+    }
+
+    pub async fn upload_file_chunked(
+        &self,
+        upload_type: UploadType,
+        path: impl AsRef<Path>,
+        options: &ChunkedUploadOptions,
+    ) -> Result<UploadedInfo> {
+        self.api_client
+            .upload_file_chunked(upload_type, path, options)
+            .await
+    }
+
+    // Video methods
+    pub async fn get_video_details(&self, video_token: &str) -> Result<VideoAttachmentDetails> {
+        self.api_client.get_video_details(video_token).await
+    }
+
     // Updates
     pub async fn get_updates(
         &self,
@@ -106,4 +358,16 @@ impl MaxBotClient {
             .get_updates(limit, timeout, marker, types)
             .await
     }
+
+    pub async fn get_raw_updates(
+        &self,
+        limit: Option<i32>,
+        timeout: Option<i32>,
+        marker: Option<i64>,
+        types: Option<Vec<String>>,
+    ) -> Result<RawUpdateList> {
+        self.api_client
+            .get_raw_updates(limit, timeout, marker, types)
+            .await
+    }
 }
@@ -0,0 +1,285 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::api::models::{BotCommand, Update};
+
+#[cfg(feature = "macros")]
+pub use max_bot_api_client_rs_macros::BotCommands;
+
+#[derive(Debug, Error)]
+pub enum CommandError {
+    #[error("Unknown command: {0}")]
+    UnknownCommand(String),
+
+    #[error("Expected {expected} arguments, got {found}")]
+    ArgumentCount { expected: usize, found: usize },
+
+    #[error("Invalid argument {index}: {value}")]
+    InvalidArgument { index: usize, value: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedCommand {
+    pub name: String,
+    pub bot_username: Option<String>,
+    pub args: Vec<String>,
+    // Текст после команды как есть, с исходными пробелами и кавычками
+    pub raw_args: String,
+}
+
+impl ParsedCommand {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim_start().strip_prefix('/')?;
+        let (head, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+
+        let (name, bot_username) = match head.split_once('@') {
+            Some((name, username)) => (name, Some(username.to_string())),
+            None => (head, None),
+        };
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            name: name.to_string(),
+            bot_username,
+            args: split_args(rest),
+            raw_args: rest.trim().to_string(),
+        })
+    }
+
+    // Полезная нагрузка deep link передаётся как аргумент команды /start
+    pub fn from_start_payload(payload: Option<&str>) -> Self {
+        Self {
+            name: "start".to_string(),
+            bot_username: None,
+            args: payload.map(split_args).unwrap_or_default(),
+            raw_args: payload.unwrap_or_default().trim().to_string(),
+        }
+    }
+
+    pub fn from_update(update: &Update) -> Option<Self> {
+        match update {
+            Update::BotStarted(update) => Some(Self::from_start_payload(update.payload.as_deref())),
+            // Правки и нажатия кнопок несут уже отправленное сообщение, командами они не являются
+            Update::MessageCreated(update) => Self::parse(update.message.body.text.as_deref()?),
+            _ => None,
+        }
+    }
+
+    pub fn is_addressed_to(&self, bot_username: Option<&str>) -> bool {
+        match (&self.bot_username, bot_username) {
+            (None, _) => true,
+            (Some(mention), Some(username)) => mention.eq_ignore_ascii_case(username),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+pub trait CommandArgs: Sized {
+    fn parse_args(args: &[String]) -> Result<Self, CommandError>;
+}
+
+impl CommandArgs for () {
+    fn parse_args(_args: &[String]) -> Result<Self, CommandError> {
+        Ok(())
+    }
+}
+
+impl CommandArgs for String {
+    fn parse_args(args: &[String]) -> Result<Self, CommandError> {
+        Ok(args.join(" "))
+    }
+}
+
+impl<T: FromStr> CommandArgs for Vec<T> {
+    fn parse_args(args: &[String]) -> Result<Self, CommandError> {
+        args.iter()
+            .enumerate()
+            .map(|(index, arg)| parse_arg(index, arg))
+            .collect()
+    }
+}
+
+macro_rules! impl_command_args {
+    ($count:expr; $($arg:ident: $index:tt),*) => {
+        impl<$($arg: FromStr),*> CommandArgs for ($($arg,)*) {
+            fn parse_args(args: &[String]) -> Result<Self, CommandError> {
+                if args.len() != $count {
+                    return Err(CommandError::ArgumentCount {
+                        expected: $count,
+                        found: args.len(),
+                    });
+                }
+
+                Ok(($(parse_arg::<$arg>($index, &args[$index])?,)*))
+            }
+        }
+    };
+}
+
+impl_command_args!(1; A: 0);
+impl_command_args!(2; A: 0, B: 1);
+impl_command_args!(3; A: 0, B: 1, C: 2);
+impl_command_args!(4; A: 0, B: 1, C: 2, D: 3);
+impl_command_args!(5; A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_command_args!(6; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+
+pub fn parse_arg<T: FromStr>(index: usize, value: &str) -> Result<T, CommandError> {
+    value.parse().map_err(|_| CommandError::InvalidArgument {
+        index,
+        value: value.to_string(),
+    })
+}
+
+pub trait BotCommands: Sized {
+    fn parse(command: &ParsedCommand) -> Result<Self, CommandError>;
+
+    fn bot_commands() -> Vec<BotCommand>;
+}
+
+// Разбивает строку на аргументы с учётом кавычек и экранирования
+pub fn split_args(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut in_arg = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', _) => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+                in_arg = true;
+            }
+            // Кавычка открывает аргумент только в его начале, внутри слова это апостроф
+            ('"' | '\'', None) if !in_arg => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (c, Some(q)) if c == q => quote = None,
+            (c, None) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (c, _) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if in_arg {
+        args.push(current);
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{ParsedCommand, split_args};
+    use crate::api::models::Update;
+
+    fn update(update_type: &str, text: &str) -> Update {
+        let user = json!({
+            "user_id": 1,
+            "first_name": "User",
+            "is_bot": false,
+            "last_activity_time": 0,
+        });
+        let message = json!({
+            "sender": user,
+            "recipient": { "chat_id": 10, "chat_type": "chat" },
+            "timestamp": 0,
+            "body": { "mid": "mid.1", "seq": 1, "text": text },
+        });
+
+        serde_json::from_value(json!({
+            "update_type": update_type,
+            "timestamp": 0,
+            "message": message,
+            "callback": { "timestamp": 0, "callback_id": "cb", "user": user },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn splits_on_whitespace() {
+        assert_eq!(split_args("  a  b\tc "), ["a", "b", "c"]);
+        assert!(split_args("   ").is_empty());
+    }
+
+    #[test]
+    fn quotes_group_arguments() {
+        assert_eq!(
+            split_args(r#""big world" 'x y' z"#),
+            ["big world", "x y", "z"]
+        );
+        assert_eq!(split_args(r#""" a"#), ["", "a"]);
+    }
+
+    #[test]
+    fn backslash_escapes_next_character() {
+        assert_eq!(split_args(r#"a\ b \"c"#), ["a b", "\"c"]);
+    }
+
+    #[test]
+    fn apostrophes_inside_words_are_literal() {
+        assert_eq!(
+            split_args("don't worry, it's fine"),
+            ["don't", "worry,", "it's", "fine"]
+        );
+        assert_eq!(split_args(r#"say "it's ok""#), ["say", "it's ok"]);
+    }
+
+    #[test]
+    fn parses_command_with_mention() {
+        let command = ParsedCommand::parse("/help@MyBot topic").unwrap();
+
+        assert_eq!(command.name, "help");
+        assert_eq!(command.bot_username.as_deref(), Some("MyBot"));
+        assert_eq!(command.args, ["topic"]);
+
+        let command = ParsedCommand::parse("/say  hello  \"big world\" ").unwrap();
+        assert_eq!(command.raw_args, "hello  \"big world\"");
+    }
+
+    #[test]
+    fn rejects_non_commands_and_empty_names() {
+        assert!(ParsedCommand::parse("hello").is_none());
+        assert!(ParsedCommand::parse("/").is_none());
+        assert!(ParsedCommand::parse("/@bot").is_none());
+        assert!(ParsedCommand::parse("/ start").is_none());
+    }
+
+    #[test]
+    fn checks_addressing() {
+        let plain = ParsedCommand::parse("/start").unwrap();
+        assert!(plain.is_addressed_to(None));
+        assert!(plain.is_addressed_to(Some("bot")));
+
+        let mentioned = ParsedCommand::parse("/start@MyBot").unwrap();
+        assert!(mentioned.is_addressed_to(Some("mybot")));
+        assert!(!mentioned.is_addressed_to(Some("other_bot")));
+        assert!(!mentioned.is_addressed_to(None));
+    }
+
+    #[test]
+    fn commands_come_only_from_new_messages() {
+        let created = ParsedCommand::from_update(&update("message_created", "/help me"));
+        assert_eq!(created.unwrap().name, "help");
+
+        for update_type in ["message_callback", "message_edited"] {
+            let update = update(update_type, "/help shows commands");
+            assert!(ParsedCommand::from_update(&update).is_none());
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    ops::Deref,
+    sync::Arc,
+};
+
+use super::interceptor::Interceptor;
+use crate::{
+    api::models::{
+        BotAddedToChatUpdate, BotRemovedFromChatUpdate, BotStartedUpdate, Callback,
+        ChatTitleChangedUpdate, Message, MessageCallbackUpdate, MessageChatCreatedUpdate,
+        MessageCreatedUpdate, MessageEditedUpdate, MessageRemovedUpdate, Update, User,
+        UserAddedToChatUpdate, UserRemovedFromChatUpdate,
+    },
+    client::MaxBotClient,
+    command::{BotCommands, CommandArgs, ParsedCommand},
+    fsm::{Dialogue, StateStorage, StorageKey},
+};
+
+pub(crate) type StateMap = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
+
+pub struct UpdateContext {
+    pub client: MaxBotClient,
+    pub update: Update,
+    pub(crate) state: Arc<StateMap>,
+    pub(crate) bot_username: Option<Arc<str>>,
+    pub(crate) storage: Option<Arc<dyn StateStorage>>,
+    pub(crate) fsm_state: Option<String>,
+    pub(crate) interceptor: Interceptor,
+}
+
+impl UpdateContext {
+    pub fn bot_username(&self) -> Option<&str> {
+        self.bot_username.as_deref()
+    }
+
+    pub fn fsm_state(&self) -> Option<&str> {
+        self.fsm_state.as_deref()
+    }
+
+    pub fn command(&self) -> Option<ParsedCommand> {
+        ParsedCommand::from_update(&self.update)
+            .filter(|command| command.is_addressed_to(self.bot_username()))
+    }
+}
+
+pub trait FromUpdate: Sized {
+    fn from_update(ctx: &UpdateContext) -> Option<Self>;
+}
+
+impl FromUpdate for MaxBotClient {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        Some(ctx.client.clone())
+    }
+}
+
+impl FromUpdate for Update {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        Some(ctx.update.clone())
+    }
+}
+
+impl FromUpdate for Message {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        ctx.update.message().cloned()
+    }
+}
+
+impl FromUpdate for Callback {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        match &ctx.update {
+            Update::MessageCallback(update) => Some(update.callback.clone()),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_from_update_for_variant {
+    ($($variant:ident($update:ty)),* $(,)?) => {
+        $(
+            impl FromUpdate for $update {
+                fn from_update(ctx: &UpdateContext) -> Option<Self> {
+                    match &ctx.update {
+                        Update::$variant(update) => Some(update.clone()),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_update_for_variant!(
+    MessageCreated(MessageCreatedUpdate),
+    MessageCallback(MessageCallbackUpdate),
+    MessageEdited(MessageEditedUpdate),
+    MessageRemoved(MessageRemovedUpdate),
+    BotAdded(BotAddedToChatUpdate),
+    BotRemoved(BotRemovedFromChatUpdate),
+    UserAdded(UserAddedToChatUpdate),
+    UserRemoved(UserRemovedFromChatUpdate),
+    BotStarted(BotStartedUpdate),
+    ChatTitleChanged(ChatTitleChangedUpdate),
+    MessageChatCreated(MessageChatCreatedUpdate),
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChatId(pub i64);
+
+impl FromUpdate for ChatId {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        ctx.update.chat_id().map(ChatId)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Sender(pub User);
+
+impl FromUpdate for Sender {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        ctx.update.user().cloned().map(Sender)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserLocale(pub String);
+
+impl FromUpdate for UserLocale {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        ctx.update
+            .user_locale()
+            .map(|locale| UserLocale(locale.to_string()))
+    }
+}
+
+#[derive(Debug)]
+pub struct State<T>(pub Arc<T>);
+
+impl<T> Clone for State<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Deref for State<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Send + Sync + 'static> FromUpdate for State<T> {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        let state = ctx.state.get(&TypeId::of::<T>())?.clone();
+        state.downcast::<T>().ok().map(State)
+    }
+}
+
+impl FromUpdate for Dialogue {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        let storage = ctx.storage.clone()?;
+        let key = StorageKey::from_update(&ctx.update)?;
+
+        Some(Dialogue::new(storage, key))
+    }
+}
+
+impl FromUpdate for ParsedCommand {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        ctx.command()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Command<Args = ()> {
+    pub name: String,
+    pub args: Args,
+}
+
+impl<Args: CommandArgs> FromUpdate for Command<Args> {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        let command = ctx.command()?;
+
+        Some(Command {
+            args: Args::parse_args(&command.args).ok()?,
+            name: command.name,
+        })
+    }
+}
+
+impl<C: BotCommands> FromUpdate for C {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        C::parse(&ctx.command()?).ok()
+    }
+}
@@ -0,0 +1,129 @@
+use regex::Regex;
+
+use super::UpdateContext;
+use crate::{
+    api::models::{ChatType, Update},
+    command::ParsedCommand,
+};
+
+pub trait Filter: Send + Sync + 'static {
+    fn check(&self, update: &Update) -> bool;
+
+    // Диспетчер вызывает этот метод; фильтрам, которым нужно имя бота, стоит его переопределить
+    fn check_context(&self, ctx: &UpdateContext) -> bool {
+        self.check(&ctx.update)
+    }
+}
+
+impl<F> Filter for F
+where
+    F: Fn(&Update) -> bool + Send + Sync + 'static,
+{
+    fn check(&self, update: &Update) -> bool {
+        self(update)
+    }
+}
+
+pub trait FilterExt: Filter + Sized {
+    fn and<F: Filter>(self, other: F) -> And<Self, F> {
+        And(self, other)
+    }
+
+    fn or<F: Filter>(self, other: F) -> Or<Self, F> {
+        Or(self, other)
+    }
+
+    fn not(self) -> Not<Self> {
+        Not(self)
+    }
+}
+
+impl<F: Filter> FilterExt for F {}
+
+pub struct And<A, B>(A, B);
+
+impl<A: Filter, B: Filter> Filter for And<A, B> {
+    fn check(&self, update: &Update) -> bool {
+        self.0.check(update) && self.1.check(update)
+    }
+
+    fn check_context(&self, ctx: &UpdateContext) -> bool {
+        self.0.check_context(ctx) && self.1.check_context(ctx)
+    }
+}
+
+pub struct Or<A, B>(A, B);
+
+impl<A: Filter, B: Filter> Filter for Or<A, B> {
+    fn check(&self, update: &Update) -> bool {
+        self.0.check(update) || self.1.check(update)
+    }
+
+    fn check_context(&self, ctx: &UpdateContext) -> bool {
+        self.0.check_context(ctx) || self.1.check_context(ctx)
+    }
+}
+
+pub struct Not<A>(A);
+
+impl<A: Filter> Filter for Not<A> {
+    fn check(&self, update: &Update) -> bool {
+        !self.0.check(update)
+    }
+
+    fn check_context(&self, ctx: &UpdateContext) -> bool {
+        !self.0.check_context(ctx)
+    }
+}
+
+pub fn chat_type(chat_type: ChatType) -> impl Filter {
+    move |update: &Update| update.chat_type() == Some(chat_type)
+}
+
+pub fn chat_id(chat_id: i64) -> impl Filter {
+    move |update: &Update| update.chat_id() == Some(chat_id)
+}
+
+pub fn text_regex(pattern: &str) -> Result<impl Filter, regex::Error> {
+    let regex = Regex::new(pattern)?;
+
+    Ok(move |update: &Update| {
+        update
+            .message()
+            .and_then(|message| message.body.text.as_deref())
+            .is_some_and(|text| regex.is_match(text))
+    })
+}
+
+pub fn sender_is_bot() -> impl Filter {
+    |update: &Update| update.user().is_some_and(|user| user.is_bot)
+}
+
+pub fn command(name: &str) -> CommandFilter {
+    CommandFilter {
+        name: name.to_string(),
+    }
+}
+
+pub struct CommandFilter {
+    name: String,
+}
+
+impl CommandFilter {
+    fn matches(&self, command: Option<ParsedCommand>) -> bool {
+        command.is_some_and(|command| command.name.eq_ignore_ascii_case(&self.name))
+    }
+}
+
+impl Filter for CommandFilter {
+    // Без имени бота подходят только команды без упоминания /cmd@botname
+    fn check(&self, update: &Update) -> bool {
+        self.matches(
+            ParsedCommand::from_update(update).filter(|command| command.is_addressed_to(None)),
+        )
+    }
+
+    fn check_context(&self, ctx: &UpdateContext) -> bool {
+        self.matches(ctx.command())
+    }
+}
@@ -0,0 +1,38 @@
+use std::future::Future;
+
+use futures::future::BoxFuture;
+
+use super::extract::{FromUpdate, UpdateContext};
+use crate::api::types::Result;
+
+pub trait Handler<Args>: Send + Sync + 'static {
+    // Возвращает None, если какой-либо из аргументов не удалось извлечь
+    fn call(&self, ctx: &UpdateContext) -> Option<BoxFuture<'static, Result<()>>>;
+}
+
+macro_rules! impl_handler {
+    ($($arg:ident),*) => {
+        impl<F, Fut, $($arg,)*> Handler<($($arg,)*)> for F
+        where
+            F: Fn($($arg),*) -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = Result<()>> + Send + 'static,
+            $($arg: FromUpdate,)*
+        {
+            #[allow(non_snake_case, unused_variables)]
+            fn call(&self, ctx: &UpdateContext) -> Option<BoxFuture<'static, Result<()>>> {
+                $(let $arg = $arg::from_update(ctx)?;)*
+                Some(Box::pin(self($($arg),*)))
+            }
+        }
+    };
+}
+
+impl_handler!();
+impl_handler!(T1);
+impl_handler!(T1, T2);
+impl_handler!(T1, T2, T3);
+impl_handler!(T1, T2, T3, T4);
+impl_handler!(T1, T2, T3, T4, T5);
+impl_handler!(T1, T2, T3, T4, T5, T6);
+impl_handler!(T1, T2, T3, T4, T5, T6, T7);
+impl_handler!(T1, T2, T3, T4, T5, T6, T7, T8);
@@ -0,0 +1,263 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use tokio::sync::oneshot::{self, error::RecvError};
+
+use super::extract::{FromUpdate, UpdateContext};
+use crate::{
+    api::{
+        error::ApiClientError,
+        models::{Message, MessageCallbackUpdate, MessageCreatedUpdate, NewMessageBody, Update},
+        types::Result,
+    },
+    client::MaxBotClient,
+    fsm::StorageKey,
+};
+
+const DEFAULT_ASK_TIMEOUT: Duration = Duration::from_secs(300);
+
+struct CallbackWaiter {
+    mid: String,
+    user_id: Option<i64>,
+    sender: oneshot::Sender<MessageCallbackUpdate>,
+}
+
+struct ReplyWaiter {
+    id: u64,
+    sender: oneshot::Sender<Message>,
+}
+
+#[derive(Default)]
+struct Waiters {
+    replies: HashMap<StorageKey, ReplyWaiter>,
+    callbacks: Vec<CallbackWaiter>,
+    next_id: u64,
+}
+
+// Перехватывает обновления, которых ожидают обработчики, до передачи их маршрутам
+#[derive(Clone, Default)]
+pub struct Interceptor {
+    waiters: Arc<Mutex<Waiters>>,
+}
+
+impl Interceptor {
+    // Одновременно ждать ответа пользователя в чате может только один обработчик
+    pub fn wait_for_reply(&self, chat_id: i64, user_id: i64) -> Result<ReplyReceiver> {
+        let (sender, receiver) = oneshot::channel();
+        let key = StorageKey::new(chat_id, user_id);
+
+        let mut waiters = self.waiters.lock().unwrap();
+        if waiters
+            .replies
+            .get(&key)
+            .is_some_and(|waiter| !waiter.sender.is_closed())
+        {
+            return Err(ApiClientError::AlreadyWaiting);
+        }
+
+        let id = waiters.next_id;
+        waiters.next_id += 1;
+        waiters.replies.insert(key, ReplyWaiter { id, sender });
+
+        Ok(ReplyReceiver {
+            receiver,
+            interceptor: self.clone(),
+            key,
+            id,
+        })
+    }
+
+    pub fn wait_for_callback(
+        &self,
+        mid: &str,
+        user_id: Option<i64>,
+    ) -> oneshot::Receiver<MessageCallbackUpdate> {
+        let (sender, receiver) = oneshot::channel();
+
+        let mut waiters = self.waiters.lock().unwrap();
+        waiters
+            .callbacks
+            .retain(|waiter| !waiter.sender.is_closed());
+        waiters.callbacks.push(CallbackWaiter {
+            mid: mid.to_string(),
+            user_id,
+            sender,
+        });
+
+        receiver
+    }
+
+    pub(crate) fn intercept(&self, update: Update) -> Option<Update> {
+        match update {
+            Update::MessageCreated(created) => {
+                self.intercept_reply(created).map(Update::MessageCreated)
+            }
+            Update::MessageCallback(callback) => self
+                .intercept_callback(callback)
+                .map(Update::MessageCallback),
+            update => Some(update),
+        }
+    }
+
+    fn intercept_reply(&self, mut created: MessageCreatedUpdate) -> Option<MessageCreatedUpdate> {
+        let key = created
+            .message
+            .recipient
+            .chat_id
+            .zip(created.message.sender.as_ref().map(|user| user.user_id))
+            .map(|(chat_id, user_id)| StorageKey::new(chat_id, user_id));
+
+        let waiter = key.and_then(|key| self.waiters.lock().unwrap().replies.remove(&key));
+
+        // Если ожидающий обработчик уже завершился, обновление идёт по обычным маршрутам
+        if let Some(waiter) = waiter {
+            match waiter.sender.send(created.message) {
+                Ok(()) => return None,
+                Err(message) => created.message = message,
+            }
+        }
+
+        Some(created)
+    }
+
+    fn intercept_callback(&self, callback: MessageCallbackUpdate) -> Option<MessageCallbackUpdate> {
+        let Some(mid) = callback.message.as_ref().map(|m| m.body.mid.as_str()) else {
+            return Some(callback);
+        };
+        let user_id = callback.callback.user.user_id;
+
+        let waiter = {
+            let mut waiters = self.waiters.lock().unwrap();
+            waiters
+                .callbacks
+                .iter()
+                .position(|waiter| {
+                    waiter.mid == mid
+                        && waiter.user_id.is_none_or(|id| id == user_id)
+                        && !waiter.sender.is_closed()
+                })
+                .map(|index| waiters.callbacks.swap_remove(index))
+        };
+
+        match waiter {
+            Some(waiter) => waiter.sender.send(callback).err(),
+            None => Some(callback),
+        }
+    }
+}
+
+impl AsRef<Interceptor> for Interceptor {
+    fn as_ref(&self) -> &Interceptor {
+        self
+    }
+}
+
+// Снимает ожидание ответа, когда получатель отброшен, например по тайм-ауту
+pub struct ReplyReceiver {
+    receiver: oneshot::Receiver<Message>,
+    interceptor: Interceptor,
+    key: StorageKey,
+    id: u64,
+}
+
+impl Future for ReplyReceiver {
+    type Output = std::result::Result<Message, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver).poll(cx)
+    }
+}
+
+impl Drop for ReplyReceiver {
+    fn drop(&mut self) {
+        let mut waiters = self.interceptor.waiters.lock().unwrap();
+        if waiters
+            .replies
+            .get(&self.key)
+            .is_some_and(|waiter| waiter.id == self.id)
+        {
+            waiters.replies.remove(&self.key);
+        }
+    }
+}
+
+pub(crate) async fn wait_for<T>(
+    receiver: impl Future<Output = std::result::Result<T, RecvError>>,
+    timeout: Duration,
+) -> Result<T> {
+    match tokio::time::timeout(timeout, receiver).await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(_)) => Err(ApiClientError::WaitCancelled),
+        Err(_) => Err(ApiClientError::Timeout),
+    }
+}
+
+#[derive(Clone)]
+pub struct Conversation {
+    client: MaxBotClient,
+    interceptor: Interceptor,
+    timeout: Duration,
+}
+
+impl Conversation {
+    pub fn new(client: MaxBotClient, interceptor: Interceptor) -> Self {
+        Self {
+            client,
+            interceptor,
+            timeout: DEFAULT_ASK_TIMEOUT,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub async fn ask(&self, chat_id: i64, user_id: i64, text: &str) -> Result<Message> {
+        let message = NewMessageBody::new(text.into());
+        self.ask_with(chat_id, user_id, &message).await
+    }
+
+    pub async fn ask_with(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        message: &NewMessageBody,
+    ) -> Result<Message> {
+        // Ожидание регистрируется до отправки, чтобы не пропустить быстрый ответ
+        let reply = self.interceptor.wait_for_reply(chat_id, user_id)?;
+
+        self.client
+            .api_client
+            .send_message(message, Some(chat_id), None)
+            .await?;
+
+        wait_for(reply, self.timeout).await
+    }
+
+    pub async fn wait_for_reply(&self, chat_id: i64, user_id: i64) -> Result<Message> {
+        let reply = self.interceptor.wait_for_reply(chat_id, user_id)?;
+        wait_for(reply, self.timeout).await
+    }
+}
+
+impl FromUpdate for Interceptor {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        Some(ctx.interceptor.clone())
+    }
+}
+
+impl FromUpdate for Conversation {
+    fn from_update(ctx: &UpdateContext) -> Option<Self> {
+        Some(Conversation::new(
+            ctx.client.clone(),
+            ctx.interceptor.clone(),
+        ))
+    }
+}
@@ -0,0 +1,521 @@
+pub mod extract;
+pub mod filter;
+mod handler;
+pub(crate) mod interceptor;
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    marker::PhantomData,
+    sync::Arc,
+};
+
+use futures::future::BoxFuture;
+use tokio::{
+    sync::{
+        mpsc,
+        oneshot::{self, error::TryRecvError},
+    },
+    task::{JoinError, JoinSet},
+};
+
+use crate::{
+    api::{
+        error::ApiClientError,
+        models::{Update, UpdateKind},
+        types::Result,
+    },
+    client::MaxBotClient,
+    fsm::{StateFilter, StateStorage, StorageKey},
+};
+
+pub use extract::{ChatId, Command, FromUpdate, Sender, State, UpdateContext, UserLocale};
+pub use filter::{Filter, FilterExt};
+pub use handler::Handler;
+pub use interceptor::{Conversation, Interceptor, ReplyReceiver};
+
+use extract::StateMap;
+
+type BoxHandler = Box<dyn ErasedHandler>;
+// Завершённые очереди пользователей удаляются, когда их становится больше этого числа
+const PENDING_KEYS_PRUNE_THRESHOLD: usize = 1024;
+
+type ErrorHandler = Arc<dyn Fn(ApiClientError) + Send + Sync>;
+
+trait ErasedHandler: Send + Sync {
+    fn call(&self, ctx: &UpdateContext) -> Option<BoxFuture<'static, Result<()>>>;
+}
+
+struct HandlerFn<H, Args> {
+    handler: H,
+    _args: PhantomData<fn() -> Args>,
+}
+
+impl<H, Args> ErasedHandler for HandlerFn<H, Args>
+where
+    H: Handler<Args>,
+    Args: 'static,
+{
+    fn call(&self, ctx: &UpdateContext) -> Option<BoxFuture<'static, Result<()>>> {
+        self.handler.call(ctx)
+    }
+}
+
+pub struct Route {
+    kind: Option<UpdateKind>,
+    filters: Vec<Box<dyn Filter>>,
+    state: StateFilter,
+    handler: BoxHandler,
+}
+
+impl Route {
+    pub fn filter(&mut self, filter: impl Filter) -> &mut Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    pub fn state(&mut self, state: impl Into<String>) -> &mut Self {
+        self.state = StateFilter::Is(state.into());
+        self
+    }
+
+    pub fn state_filter(&mut self, state: StateFilter) -> &mut Self {
+        self.state = state;
+        self
+    }
+
+    fn matches(&self, ctx: &UpdateContext) -> bool {
+        self.kind.is_none_or(|kind| kind == ctx.update.kind())
+            && self.state.check(ctx.fsm_state())
+            && self.filters.iter().all(|filter| filter.check_context(ctx))
+    }
+}
+
+macro_rules! typed_route {
+    ($method:ident, $variant:ident) => {
+        pub fn $method<H, Args>(&mut self, handler: H) -> &mut Route
+        where
+            H: Handler<Args>,
+            Args: 'static,
+        {
+            self.push_route(Some(UpdateKind::$variant), box_handler(handler))
+        }
+    };
+}
+
+pub struct Dispatcher {
+    client: MaxBotClient,
+    routes: Vec<Route>,
+    fallback: Option<BoxHandler>,
+    error_handler: Option<ErrorHandler>,
+    state: Arc<StateMap>,
+    bot_username: Option<Arc<str>>,
+    storage: Option<Arc<dyn StateStorage>>,
+    interceptor: Interceptor,
+}
+
+impl Dispatcher {
+    pub fn new(client: MaxBotClient) -> Self {
+        Self {
+            client,
+            routes: Vec::new(),
+            fallback: None,
+            error_handler: None,
+            state: Arc::new(StateMap::new()),
+            bot_username: None,
+            storage: None,
+            interceptor: Interceptor::default(),
+        }
+    }
+
+    pub fn state<T: Send + Sync + 'static>(&mut self, value: T) -> &mut Self {
+        let value: Arc<dyn Any + Send + Sync> = Arc::new(value);
+        Arc::make_mut(&mut self.state).insert(TypeId::of::<T>(), value);
+        self
+    }
+
+    pub fn bot_username(&mut self, username: impl Into<String>) -> &mut Self {
+        self.bot_username = Some(username.into().into());
+        self
+    }
+
+    pub fn storage(&mut self, storage: impl StateStorage) -> &mut Self {
+        self.storage = Some(Arc::new(storage));
+        self
+    }
+
+    pub fn interceptor(&self) -> Interceptor {
+        self.interceptor.clone()
+    }
+
+    pub fn on<H, Args>(&mut self, handler: H) -> &mut Route
+    where
+        H: Handler<Args>,
+        Args: 'static,
+    {
+        self.push_route(None, box_handler(handler))
+    }
+
+    typed_route!(on_message_created, MessageCreated);
+    typed_route!(on_message_callback, MessageCallback);
+    typed_route!(on_message_edited, MessageEdited);
+    typed_route!(on_message_removed, MessageRemoved);
+    typed_route!(on_bot_added, BotAdded);
+    typed_route!(on_bot_removed, BotRemoved);
+    typed_route!(on_user_added, UserAdded);
+    typed_route!(on_user_removed, UserRemoved);
+    typed_route!(on_bot_started, BotStarted);
+    typed_route!(on_chat_title_changed, ChatTitleChanged);
+    typed_route!(on_message_chat_created, MessageChatCreated);
+
+    pub fn fallback<H, Args>(&mut self, handler: H)
+    where
+        H: Handler<Args>,
+        Args: 'static,
+    {
+        self.fallback = Some(box_handler(handler));
+    }
+
+    // Без обработчика ошибки обработки обновлений отбрасываются
+    pub fn on_error<H>(&mut self, handler: H)
+    where
+        H: Fn(ApiClientError) + Send + Sync + 'static,
+    {
+        self.error_handler = Some(Arc::new(handler));
+    }
+
+    pub async fn dispatch(&self, update: Update) -> Result<()> {
+        match self.interceptor.intercept(update) {
+            Some(update) => self.handle(update).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn handle(&self, update: Update) -> Result<()> {
+        let fsm_state = match (&self.storage, StorageKey::from_update(&update)) {
+            (Some(storage), Some(key)) => storage.get(key).await?.and_then(|r| r.state),
+            _ => None,
+        };
+
+        let ctx = UpdateContext {
+            client: self.client.clone(),
+            update,
+            state: self.state.clone(),
+            bot_username: self.bot_username.clone(),
+            storage: self.storage.clone(),
+            fsm_state,
+            interceptor: self.interceptor.clone(),
+        };
+
+        // Обработчики, аргументы которых не извлекаются из обновления, пропускаются
+        let future = self
+            .routes
+            .iter()
+            .filter(|route| route.matches(&ctx))
+            .find_map(|route| route.handler.call(&ctx))
+            .or_else(|| self.fallback.as_ref()?.call(&ctx));
+
+        match future {
+            Some(future) => future.await,
+            None => Ok(()),
+        }
+    }
+
+    pub async fn run(mut self, mut receiver: mpsc::Receiver<Update>) {
+        // Имя бота нужно, чтобы распознавать команды вида /cmd@botname
+        if self.bot_username.is_none() {
+            match self.client.get_my_info().await {
+                Ok(info) => self.bot_username = info.username.map(Into::into),
+                Err(e) => self.handle_error(e),
+            }
+        }
+
+        let dispatcher = Arc::new(self);
+        let mut tasks = JoinSet::new();
+        let mut pending: HashMap<StorageKey, oneshot::Receiver<()>> = HashMap::new();
+
+        while let Some(update) = receiver.recv().await {
+            // Ответы на ask и нажатия ожидаемых кнопок перехватываются до очереди пользователя,
+            // иначе обработчик, ждущий ответа, заблокировал бы его доставку
+            let Some(update) = dispatcher.interceptor.intercept(update) else {
+                continue;
+            };
+
+            if pending.len() >= PENDING_KEYS_PRUNE_THRESHOLD {
+                pending.retain(|_, done| matches!(done.try_recv(), Err(TryRecvError::Empty)));
+            }
+
+            // Обновления одного пользователя в одном чате обрабатываются по порядку,
+            // остальные — параллельно
+            let (done_tx, done_rx) = oneshot::channel();
+            let previous = match StorageKey::from_update(&update) {
+                Some(key) => pending.insert(key, done_rx),
+                None => None,
+            };
+
+            let task_dispatcher = dispatcher.clone();
+            tasks.spawn(async move {
+                if let Some(previous) = previous {
+                    let _ = previous.await;
+                }
+
+                if let Err(e) = task_dispatcher.handle(update).await {
+                    task_dispatcher.handle_error(e);
+                }
+
+                drop(done_tx);
+            });
+
+            while let Some(result) = tasks.try_join_next() {
+                dispatcher.handle_join(result);
+            }
+        }
+
+        // Дожидаемся обработчиков, начатых до закрытия канала
+        while let Some(result) = tasks.join_next().await {
+            dispatcher.handle_join(result);
+        }
+    }
+
+    fn push_route(&mut self, kind: Option<UpdateKind>, handler: BoxHandler) -> &mut Route {
+        self.routes.push(Route {
+            kind,
+            filters: Vec::new(),
+            state: StateFilter::Any,
+            handler,
+        });
+        self.routes.last_mut().unwrap()
+    }
+
+    fn handle_error(&self, error: ApiClientError) {
+        if let Some(handler) = &self.error_handler {
+            handler(error);
+        }
+    }
+
+    // Паника обработчика не останавливает диспетчер, но передаётся в обработчик ошибок
+    fn handle_join(&self, result: std::result::Result<(), JoinError>) {
+        let Err(error) = result else {
+            return;
+        };
+        let Ok(payload) = error.try_into_panic() else {
+            return;
+        };
+
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "unknown panic payload".to_string(),
+            },
+        };
+        self.handle_error(ApiClientError::HandlerPanic(message));
+    }
+}
+
+impl AsRef<Interceptor> for Dispatcher {
+    fn as_ref(&self) -> &Interceptor {
+        &self.interceptor
+    }
+}
+
+fn box_handler<H, Args>(handler: H) -> BoxHandler
+where
+    H: Handler<Args>,
+    Args: 'static,
+{
+    Box::new(HandlerFn {
+        handler,
+        _args: PhantomData,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use futures::future::{Ready, ready};
+    use serde_json::json;
+    use tokio::sync::mpsc;
+
+    use super::{Dispatcher, filter};
+    use crate::{
+        api::{
+            error::ApiClientError,
+            models::{Callback, Update},
+            types::Result,
+        },
+        client::MaxBotClient,
+    };
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    fn dispatcher() -> Dispatcher {
+        let client =
+            MaxBotClient::with_base_url("token".to_string(), "http://127.0.0.1:1").unwrap();
+        let mut dispatcher = Dispatcher::new(client);
+        dispatcher.bot_username("bot");
+        dispatcher
+    }
+
+    fn user(user_id: i64) -> serde_json::Value {
+        json!({
+            "user_id": user_id,
+            "first_name": "User",
+            "is_bot": false,
+            "last_activity_time": 0,
+        })
+    }
+
+    fn bot_started(user_id: i64, payload: &str) -> Update {
+        serde_json::from_value(json!({
+            "update_type": "bot_started",
+            "timestamp": 0,
+            "chat_id": 10,
+            "user": user(user_id),
+            "payload": payload,
+        }))
+        .unwrap()
+    }
+
+    fn message(text: &str) -> Update {
+        serde_json::from_value(json!({
+            "update_type": "message_created",
+            "timestamp": 0,
+            "message": {
+                "sender": user(1),
+                "recipient": { "chat_id": 10, "chat_type": "chat" },
+                "timestamp": 0,
+                "body": { "mid": "mid.1", "seq": 1, "text": text },
+            },
+        }))
+        .unwrap()
+    }
+
+    fn payload(update: &Update) -> String {
+        match update {
+            Update::BotStarted(update) => update.payload.clone().unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+
+    fn record(
+        log: &Log,
+        name: &'static str,
+    ) -> impl Fn(Update) -> Ready<Result<()>> + Send + Sync + 'static {
+        let log = log.clone();
+        move |_| {
+            log.lock().unwrap().push(name.to_string());
+            ready(Ok(()))
+        }
+    }
+
+    async fn run(dispatcher: Dispatcher, updates: Vec<Update>) {
+        let (sender, receiver) = mpsc::channel(updates.len().max(1));
+        for update in updates {
+            sender.send(update).await.unwrap();
+        }
+        drop(sender);
+
+        dispatcher.run(receiver).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn updates_of_one_user_are_handled_in_order() {
+        let log = Log::default();
+        let mut dispatcher = dispatcher();
+
+        let handler_log = log.clone();
+        dispatcher.on_bot_started(move |update: Update| {
+            let log = handler_log.clone();
+            async move {
+                // Первые обновления пользователя обрабатываются дольше последующих
+                let payload = payload(&update);
+                let delay = match payload.as_str() {
+                    "a1" => 30,
+                    "b1" => 20,
+                    "a2" => 10,
+                    _ => 5,
+                };
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                log.lock().unwrap().push(payload);
+                Ok(())
+            }
+        });
+
+        let updates = vec![
+            bot_started(1, "a1"),
+            bot_started(1, "a2"),
+            bot_started(2, "b1"),
+            bot_started(1, "a3"),
+        ];
+        run(dispatcher, updates).await;
+
+        // Обновление другого пользователя не ждёт очереди первого
+        assert_eq!(*log.lock().unwrap(), ["b1", "a1", "a2", "a3"]);
+    }
+
+    #[tokio::test]
+    async fn first_matching_route_handles_update() {
+        let log = Log::default();
+        let mut dispatcher = dispatcher();
+
+        // Callback не извлекается из сообщения, маршрут пропускается
+        let callback_log = log.clone();
+        dispatcher.on_message_created(move |_: Callback| {
+            callback_log.lock().unwrap().push("callback".to_string());
+            async { Ok(()) }
+        });
+        dispatcher
+            .on_message_created(record(&log, "start"))
+            .filter(filter::command("start"));
+        dispatcher.on_bot_started(record(&log, "bot_started"));
+        dispatcher
+            .on(record(&log, "any_message"))
+            .filter(filter::chat_id(20));
+        dispatcher.fallback(record(&log, "fallback"));
+
+        dispatcher.dispatch(message("/start")).await.unwrap();
+        dispatcher.dispatch(bot_started(1, "")).await.unwrap();
+        dispatcher.dispatch(message("hello")).await.unwrap();
+
+        assert_eq!(*log.lock().unwrap(), ["start", "bot_started", "fallback"]);
+    }
+
+    #[tokio::test]
+    async fn handler_panic_is_reported() {
+        let log = Log::default();
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let mut dispatcher = dispatcher();
+
+        let handler_log = log.clone();
+        dispatcher.on_bot_started(move |update: Update| {
+            let log = handler_log.clone();
+            async move {
+                let payload = payload(&update);
+                if payload == "panic" {
+                    panic!("handler failed");
+                }
+                log.lock().unwrap().push(payload);
+                Ok(())
+            }
+        });
+        let observed = errors.clone();
+        dispatcher.on_error(move |error| observed.lock().unwrap().push(error));
+
+        run(
+            dispatcher,
+            vec![bot_started(1, "panic"), bot_started(1, "ok")],
+        )
+        .await;
+
+        // Очередь пользователя не застревает на упавшем обработчике
+        assert_eq!(*log.lock().unwrap(), ["ok"]);
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            matches!(&errors[0], ApiClientError::HandlerPanic(message) if message == "handler failed")
+        );
+    }
+}
@@ -1,11 +1,82 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde_json::Value;
 use tokio::sync::mpsc;
+use tokio::time::{Instant, sleep, timeout_at};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    api::{error::ApiClientError, models::Update, retry::RetryPolicy, types::Result},
+    client::MaxBotClient,
+};
+
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+type ErrorObserver = Arc<dyn Fn(&PollingError) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollingErrorKind {
+    Network,
+    Server,
+    Unauthorized,
+    Deserialize,
+    Other,
+}
+
+impl PollingErrorKind {
+    pub fn classify(error: &ApiClientError) -> Self {
+        match error {
+            ApiClientError::JsonError(_) => Self::Deserialize,
+            ApiClientError::RequestError(e) if e.is_decode() => Self::Deserialize,
+            _ if error.status() == Some(StatusCode::UNAUTHORIZED) => Self::Unauthorized,
+            _ if error.status().is_some() && error.is_transient() => Self::Server,
+            ApiClientError::RequestError(_) | ApiClientError::Middleware(_)
+                if error.is_transient() =>
+            {
+                Self::Network
+            }
+            _ => Self::Other,
+        }
+    }
+
+    pub fn is_fatal(self) -> bool {
+        matches!(self, Self::Unauthorized | Self::Other)
+    }
+}
+
+#[derive(Debug)]
+pub struct PollingError {
+    pub kind: PollingErrorKind,
+    pub error: ApiClientError,
+    // Задержка перед следующим опросом; None для фатальных ошибок и пропущенных обновлений
+    pub retry_in: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle {
+    token: CancellationToken,
+}
+
+impl ShutdownHandle {
+    pub fn shutdown(&self) {
+        self.token.cancel();
+    }
 
-use crate::{api::models::Update, api::types::Result, client::MaxBotClient};
+    pub fn is_shutdown(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
 
 pub struct EventHandler {
     client: MaxBotClient,
     marker: Option<i64>,
     event_sender: mpsc::Sender<Update>,
+    retry_policy: RetryPolicy,
+    error_observer: Option<ErrorObserver>,
+    shutdown: CancellationToken,
+    drain_timeout: Duration,
 }
 
 impl EventHandler {
@@ -13,28 +84,291 @@ impl EventHandler {
         let (sender, receiver) = mpsc::channel(100);
         (
             Self {
-                client,
+                // Повторы выполняет сам опрос, чтобы наблюдатель видел каждую ошибку
+                client: client.with_retry_policy(RetryPolicy::disabled()),
                 marker: None,
                 event_sender: sender,
+                retry_policy: RetryPolicy {
+                    max_retries: u32::MAX,
+                    base_delay: Duration::from_secs(1),
+                    max_delay: Duration::from_secs(60),
+                    retry_non_idempotent: false,
+                },
+                error_observer: None,
+                shutdown: CancellationToken::new(),
+                drain_timeout: Duration::from_secs(10),
             },
             receiver,
         )
     }
 
-    pub async fn start(&mut self) -> Result<()> {
+    // max_retries ограничивает число неудачных опросов подряд
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    pub fn on_error<F>(&mut self, observer: F)
+    where
+        F: Fn(&PollingError) + Send + Sync + 'static,
+    {
+        self.error_observer = Some(Arc::new(observer));
+    }
+
+    pub fn marker(&self) -> Option<i64> {
+        self.marker
+    }
+
+    // Позволяет продолжить опрос с маркера, сохранённого при прошлой остановке
+    pub fn set_marker(&mut self, marker: Option<i64>) {
+        self.marker = marker;
+    }
+
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle {
+            token: self.shutdown.clone(),
+        }
+    }
+
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.shutdown = token;
+    }
+
+    // Общее время на доставку текущей пачки и разбор канала после остановки
+    pub fn set_drain_timeout(&mut self, timeout: Duration) {
+        self.drain_timeout = timeout;
+    }
+
+    // Возвращает маркер, до которого все обновления переданы в канал
+    pub async fn start(&mut self) -> Result<Option<i64>> {
+        let mut failures = 0;
+        let mut deadline = None;
+
         loop {
-            let updates = self
-                .client
-                .get_updates(Some(100), Some(30), self.marker, None)
-                .await?;
-
-            // Отправляем все обновления через канал
-            for update in updates.updates {
-                self.event_sender.send(update).await?;
-            }
+            let result = tokio::select! {
+                biased;
+                _ = self.shutdown.cancelled() => break,
+                result = self
+                    .client
+                    .get_raw_updates(Some(100), Some(30), self.marker, None) => result,
+            };
+
+            let updates = match result {
+                Ok(updates) => {
+                    failures = 0;
+                    updates
+                }
+                Err(error) => {
+                    let kind = PollingErrorKind::classify(&error);
+                    if kind.is_fatal() || failures >= self.retry_policy.max_retries {
+                        return Err(self.report(kind, error, None));
+                    }
 
-            // Обновляем маркер для следующего запроса
+                    let delay = self.retry_policy.backoff(failures);
+                    self.report(kind, error, Some(delay));
+
+                    failures += 1;
+                    tokio::select! {
+                        _ = self.shutdown.cancelled() => break,
+                        _ = sleep(delay) => continue,
+                    }
+                }
+            };
+
+            // Маркер сдвигается только после доставки всей пачки, иначе её получим повторно
+            if !self.deliver(updates.updates, &mut deadline).await? {
+                return Ok(self.marker);
+            }
             self.marker = updates.marker;
+
+            if self.shutdown.is_cancelled() {
+                break;
+            }
+        }
+
+        let deadline = *deadline.get_or_insert_with(|| Instant::now() + self.drain_timeout);
+        self.drain(deadline).await;
+
+        Ok(self.marker)
+    }
+
+    // Возвращает false, если пачку не удалось доставить до истечения времени на остановку
+    async fn deliver(&self, updates: Vec<Value>, deadline: &mut Option<Instant>) -> Result<bool> {
+        for raw in updates {
+            // Некорректное обновление пропускаем, чтобы не останавливать опрос
+            let update = match serde_json::from_value::<Update>(raw) {
+                Ok(update) => update,
+                Err(e) => {
+                    self.report(PollingErrorKind::Deserialize, e.into(), None);
+                    continue;
+                }
+            };
+
+            let send = self.event_sender.send(update);
+            tokio::pin!(send);
+
+            if deadline.is_none() {
+                tokio::select! {
+                    result = &mut send => {
+                        result?;
+                        continue;
+                    }
+                    _ = self.shutdown.cancelled() => {
+                        *deadline = Some(Instant::now() + self.drain_timeout);
+                    }
+                }
+            }
+
+            match timeout_at(deadline.unwrap_or_else(Instant::now), send).await {
+                Ok(result) => result?,
+                Err(_) => return Ok(false),
+            }
+        }
+
+        Ok(true)
+    }
+
+    async fn drain(&self, deadline: Instant) {
+        let sender = &self.event_sender;
+
+        while sender.capacity() < sender.max_capacity() && Instant::now() < deadline {
+            tokio::select! {
+                _ = sender.closed() => break,
+                _ = sleep(DRAIN_POLL_INTERVAL) => {}
+            }
+        }
+    }
+
+    fn report(
+        &self,
+        kind: PollingErrorKind,
+        error: ApiClientError,
+        retry_in: Option<Duration>,
+    ) -> ApiClientError {
+        let error = PollingError {
+            kind,
+            error,
+            retry_in,
+        };
+
+        if let Some(observer) = &self.error_observer {
+            observer(&error);
+        }
+
+        error.error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::{Value, json};
+    use tower::{BoxError, timeout::error::Elapsed};
+
+    use super::{EventHandler, PollingErrorKind};
+    use crate::{api::error::ApiClientError, client::MaxBotClient};
+
+    fn api_error(status: u16) -> ApiClientError {
+        ApiClientError::ApiError {
+            status,
+            code: status.to_string(),
+            message: String::new(),
+        }
+    }
+
+    fn bot_started(chat_id: i64) -> Value {
+        json!({
+            "update_type": "bot_started",
+            "timestamp": 0,
+            "chat_id": chat_id,
+            "user": {
+                "user_id": 1,
+                "first_name": "User",
+                "is_bot": false,
+                "last_activity_time": 0,
+            },
+        })
+    }
+
+    #[test]
+    fn unauthorized_is_fatal() {
+        let kind = PollingErrorKind::classify(&api_error(401));
+
+        assert_eq!(kind, PollingErrorKind::Unauthorized);
+        assert!(kind.is_fatal());
+    }
+
+    #[test]
+    fn server_errors_and_rate_limits_are_retried() {
+        for status in [500, 502, 503, 429] {
+            let kind = PollingErrorKind::classify(&api_error(status));
+
+            assert_eq!(kind, PollingErrorKind::Server, "status {}", status);
+            assert!(!kind.is_fatal());
+        }
+
+        assert_eq!(
+            PollingErrorKind::classify(&api_error(400)),
+            PollingErrorKind::Other
+        );
+    }
+
+    #[tokio::test]
+    async fn connection_failures_and_timeouts_are_network_errors() {
+        // На порту 1 никто не слушает, соединение отклоняется
+        let error = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+        assert!(error.is_connect());
+        assert_eq!(
+            PollingErrorKind::classify(&error.into()),
+            PollingErrorKind::Network
+        );
+
+        let elapsed: BoxError = Box::new(Elapsed::new());
+        assert_eq!(
+            PollingErrorKind::classify(&ApiClientError::Middleware(elapsed)),
+            PollingErrorKind::Network
+        );
+    }
+
+    #[tokio::test]
+    async fn decode_errors_are_deserialize_errors() {
+        let response = reqwest::Response::from(http::Response::new("not json"));
+        let error = response.json::<Value>().await.unwrap_err();
+        assert!(error.is_decode());
+        assert_eq!(
+            PollingErrorKind::classify(&error.into()),
+            PollingErrorKind::Deserialize
+        );
+
+        let error = serde_json::from_str::<Value>("{").unwrap_err();
+        assert_eq!(
+            PollingErrorKind::classify(&error.into()),
+            PollingErrorKind::Deserialize
+        );
+    }
+
+    #[tokio::test]
+    async fn malformed_update_is_skipped() {
+        let client =
+            MaxBotClient::with_base_url("token".to_string(), "http://127.0.0.1:1").unwrap();
+        let (mut handler, mut receiver) = EventHandler::new(client);
+
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let observed = errors.clone();
+        handler.on_error(move |error| observed.lock().unwrap().push(error.kind));
+
+        let updates = vec![
+            bot_started(1),
+            json!({ "update_type": "bot_started" }),
+            bot_started(2),
+        ];
+        assert!(handler.deliver(updates, &mut None).await.unwrap());
+
+        let mut chats = Vec::new();
+        while let Ok(update) = receiver.try_recv() {
+            chats.push(update.chat_id());
         }
+        assert_eq!(chats, [Some(1), Some(2)]);
+        assert_eq!(*errors.lock().unwrap(), [PollingErrorKind::Deserialize]);
     }
 }
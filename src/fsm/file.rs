@@ -0,0 +1,97 @@
+use std::{
+    collections::HashMap,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use futures::future::BoxFuture;
+use tokio::sync::Mutex;
+
+use super::{RecordUpdate, StateRecord, StateStorage, StorageKey};
+use crate::api::types::Result;
+
+// Состояния хранятся в JSON-файле и целиком перезаписываются при каждом изменении
+#[derive(Debug)]
+pub struct FileStorage {
+    path: PathBuf,
+    records: Mutex<HashMap<String, StateRecord>>,
+}
+
+impl FileStorage {
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let records = match tokio::fs::read(&path).await {
+            Ok(content) => serde_json::from_slice(&content)?,
+            Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self {
+            path,
+            records: Mutex::new(records),
+        })
+    }
+
+    async fn persist(&self, records: &HashMap<String, StateRecord>) -> Result<()> {
+        let content = serde_json::to_vec_pretty(records)?;
+        let temp_path = self.path.with_extension("tmp");
+
+        tokio::fs::write(&temp_path, content).await?;
+        tokio::fs::rename(&temp_path, &self.path).await?;
+        Ok(())
+    }
+}
+
+fn record_key(key: StorageKey) -> String {
+    format!("{}:{}", key.chat_id, key.user_id)
+}
+
+impl StateStorage for FileStorage {
+    fn get(&self, key: StorageKey) -> BoxFuture<'_, Result<Option<StateRecord>>> {
+        Box::pin(async move { Ok(self.records.lock().await.get(&record_key(key)).cloned()) })
+    }
+
+    fn set(&self, key: StorageKey, record: StateRecord) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            let mut records = self.records.lock().await;
+            records.insert(record_key(key), record);
+            self.persist(&records).await
+        })
+    }
+
+    fn remove(&self, key: StorageKey) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            let mut records = self.records.lock().await;
+            if records.remove(&record_key(key)).is_some() {
+                self.persist(&records).await?;
+            }
+            Ok(())
+        })
+    }
+
+    fn update(
+        &self,
+        key: StorageKey,
+        update: RecordUpdate,
+    ) -> BoxFuture<'_, Result<Option<StateRecord>>> {
+        Box::pin(async move {
+            let mut records = self.records.lock().await;
+            let key = record_key(key);
+
+            let previous = records.get(&key).cloned();
+            let record = update(previous.clone());
+            if record == previous {
+                return Ok(record);
+            }
+
+            match &record {
+                Some(record) => records.insert(key, record.clone()),
+                None => records.remove(&key),
+            };
+            self.persist(&records).await?;
+
+            Ok(record)
+        })
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use futures::future::BoxFuture;
+use tokio::sync::RwLock;
+
+use super::{RecordUpdate, StateRecord, StateStorage, StorageKey};
+use crate::api::types::Result;
+
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    records: RwLock<HashMap<StorageKey, StateRecord>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateStorage for MemoryStorage {
+    fn get(&self, key: StorageKey) -> BoxFuture<'_, Result<Option<StateRecord>>> {
+        Box::pin(async move { Ok(self.records.read().await.get(&key).cloned()) })
+    }
+
+    fn set(&self, key: StorageKey, record: StateRecord) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            self.records.write().await.insert(key, record);
+            Ok(())
+        })
+    }
+
+    fn remove(&self, key: StorageKey) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            self.records.write().await.remove(&key);
+            Ok(())
+        })
+    }
+
+    fn update(
+        &self,
+        key: StorageKey,
+        update: RecordUpdate,
+    ) -> BoxFuture<'_, Result<Option<StateRecord>>> {
+        Box::pin(async move {
+            let mut records = self.records.write().await;
+
+            let record = update(records.remove(&key));
+            if let Some(record) = &record {
+                records.insert(key, record.clone());
+            }
+            Ok(record)
+        })
+    }
+}
@@ -0,0 +1,132 @@
+mod file;
+mod memory;
+
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::api::{models::Update, types::Result};
+
+pub use file::FileStorage;
+pub use memory::MemoryStorage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StorageKey {
+    pub chat_id: i64,
+    pub user_id: i64,
+}
+
+impl StorageKey {
+    pub fn new(chat_id: i64, user_id: i64) -> Self {
+        Self { chat_id, user_id }
+    }
+
+    pub fn from_update(update: &Update) -> Option<Self> {
+        Some(Self::new(update.chat_id()?, update.user_id()?))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StateRecord {
+    pub state: Option<String>,
+    pub data: Value,
+}
+
+// Получает текущую запись и возвращает новую; None удаляет запись
+pub type RecordUpdate = Box<dyn FnOnce(Option<StateRecord>) -> Option<StateRecord> + Send>;
+
+pub trait StateStorage: Send + Sync + 'static {
+    fn get(&self, key: StorageKey) -> BoxFuture<'_, Result<Option<StateRecord>>>;
+
+    fn set(&self, key: StorageKey, record: StateRecord) -> BoxFuture<'_, Result<()>>;
+
+    fn remove(&self, key: StorageKey) -> BoxFuture<'_, Result<()>>;
+
+    // Чтение и запись выполняются атомарно относительно других вызовов хранилища
+    fn update(
+        &self,
+        key: StorageKey,
+        update: RecordUpdate,
+    ) -> BoxFuture<'_, Result<Option<StateRecord>>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateFilter {
+    Any,
+    Empty,
+    Is(String),
+    OneOf(Vec<String>),
+}
+
+impl StateFilter {
+    pub fn check(&self, state: Option<&str>) -> bool {
+        match self {
+            StateFilter::Any => true,
+            StateFilter::Empty => state.is_none(),
+            StateFilter::Is(expected) => state == Some(expected.as_str()),
+            StateFilter::OneOf(states) => state.is_some_and(|s| states.iter().any(|e| e == s)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Dialogue {
+    storage: Arc<dyn StateStorage>,
+    key: StorageKey,
+}
+
+impl Dialogue {
+    pub fn new(storage: Arc<dyn StateStorage>, key: StorageKey) -> Self {
+        Self { storage, key }
+    }
+
+    pub fn key(&self) -> StorageKey {
+        self.key
+    }
+
+    pub async fn state(&self) -> Result<Option<String>> {
+        Ok(self.record().await?.state)
+    }
+
+    pub async fn set_state(&self, state: impl Into<String>) -> Result<()> {
+        let state = state.into();
+
+        self.modify(move |record| record.state = Some(state)).await
+    }
+
+    pub async fn data<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        let record = self.record().await?;
+        if record.data.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(serde_json::from_value(record.data)?))
+    }
+
+    pub async fn set_data<T: Serialize>(&self, data: &T) -> Result<()> {
+        let data = serde_json::to_value(data)?;
+
+        self.modify(move |record| record.data = data).await
+    }
+
+    pub async fn finish(&self) -> Result<()> {
+        self.storage.remove(self.key).await
+    }
+
+    async fn modify(&self, f: impl FnOnce(&mut StateRecord) + Send + 'static) -> Result<()> {
+        let update: RecordUpdate = Box::new(move |record| {
+            let mut record = record.unwrap_or_default();
+            f(&mut record);
+            Some(record)
+        });
+
+        self.storage.update(self.key, update).await?;
+        Ok(())
+    }
+
+    async fn record(&self) -> Result<StateRecord> {
+        Ok(self.storage.get(self.key).await?.unwrap_or_default())
+    }
+}
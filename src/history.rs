@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use futures::{Stream, TryStreamExt, stream};
+
+use crate::{
+    api::{models::Message, types::Result},
+    client::MaxBotClient,
+};
+
+// API возвращает не больше 100 сообщений за запрос
+const MAX_PAGE_SIZE: i32 = 100;
+
+struct HistoryCursor {
+    client: MaxBotClient,
+    chat_id: i64,
+    page_size: i32,
+    from: Option<i64>,
+    // Уже выданные сообщения с временем from: следующая страница начинается с них же
+    boundary: HashSet<String>,
+    done: bool,
+}
+
+pub fn message_history(
+    client: MaxBotClient,
+    chat_id: i64,
+    page_size: i32,
+) -> impl Stream<Item = Result<Message>> + Send {
+    let cursor = HistoryCursor {
+        client,
+        chat_id,
+        page_size: page_size.clamp(1, MAX_PAGE_SIZE),
+        from: None,
+        boundary: HashSet::new(),
+        done: false,
+    };
+
+    stream::try_unfold(cursor, next_page)
+        .map_ok(|messages| stream::iter(messages.into_iter().map(Ok)))
+        .try_flatten()
+}
+
+async fn next_page(mut cursor: HistoryCursor) -> Result<Option<(Vec<Message>, HistoryCursor)>> {
+    loop {
+        if cursor.done {
+            return Ok(None);
+        }
+
+        let page = cursor
+            .client
+            .get_messages(
+                Some(cursor.chat_id),
+                None,
+                cursor.from,
+                None,
+                Some(cursor.page_size),
+            )
+            .await?;
+
+        // Сообщения приходят от новых к старым, следующая страница начинается с самого старого
+        let Some(oldest) = page.messages.iter().map(|m| m.timestamp).min() else {
+            return Ok(None);
+        };
+
+        let full = page.messages.len() >= cursor.page_size as usize;
+        cursor.done = !full;
+
+        let messages: Vec<Message> = page
+            .messages
+            .into_iter()
+            .filter(|m| !cursor.boundary.contains(&m.body.mid))
+            .collect();
+
+        if messages.is_empty() {
+            // Страница целиком из уже выданных сообщений с одним временем:
+            // расширяем её, чтобы получить остальные сообщения с этим временем
+            if full && cursor.page_size < MAX_PAGE_SIZE {
+                cursor.page_size = MAX_PAGE_SIZE;
+                continue;
+            }
+
+            // Больше MAX_PAGE_SIZE сообщений с одним временем API выдать не может, лишние пропускаются
+            cursor.from = Some(oldest - 1);
+            cursor.boundary.clear();
+            continue;
+        }
+
+        if cursor.from != Some(oldest) {
+            cursor.boundary.clear();
+        }
+        cursor.boundary.extend(
+            messages
+                .iter()
+                .filter(|m| m.timestamp == oldest)
+                .map(|m| m.body.mid.clone()),
+        );
+        cursor.from = Some(oldest);
+
+        return Ok(Some((messages, cursor)));
+    }
+}
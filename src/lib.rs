@@ -1,5 +1,15 @@
 pub mod api;
+mod attachment;
+pub mod builder;
+pub mod chat_action;
 pub mod client;
+pub mod command;
+pub mod dispatcher;
 pub mod error;
 pub mod event_handler;
+pub mod fsm;
+pub mod history;
 pub mod message;
+pub mod subscription;
+#[cfg(feature = "webhook")]
+pub mod webhook;
@@ -1,9 +1,16 @@
+use std::time::Duration;
+
 use crate::{
     api::{
         self,
-        models::{Message, MessageLinkType, NewMessageBody, NewMessageLink, SendMessageResult},
+        error::ApiClientError,
+        models::{
+            Callback, CallbackAnswer, Message, MessageCallbackUpdate, MessageLinkType,
+            NewMessageBody, NewMessageLink, SendMessageResult,
+        },
     },
     client::MaxBotClient,
+    dispatcher::{Interceptor, interceptor::wait_for},
 };
 
 type Result = api::types::Result<SendMessageResult>;
@@ -24,6 +31,15 @@ pub trait MessageExt {
         client: MaxBotClient,
         text: &str,
     ) -> impl std::future::Future<Output = Result> + Send;
+    fn pin(
+        &self,
+        client: MaxBotClient,
+        notify: bool,
+    ) -> impl std::future::Future<Output = api::types::Result<()>> + Send;
+    fn unpin(
+        &self,
+        client: MaxBotClient,
+    ) -> impl std::future::Future<Output = api::types::Result<()>> + Send;
 }
 
 impl MessageExt for Message {
@@ -63,6 +79,27 @@ impl MessageExt for Message {
             .send_message(&message, self.recipient.chat_id, None)
             .await
     }
+
+    async fn pin(&self, client: MaxBotClient, notify: bool) -> api::types::Result<()> {
+        let chat_id = self
+            .recipient
+            .chat_id
+            .ok_or(ApiClientError::MissingChatId)?;
+
+        client
+            .api_client
+            .pin_message(chat_id, &self.body.mid, notify)
+            .await
+    }
+
+    async fn unpin(&self, client: MaxBotClient) -> api::types::Result<()> {
+        let chat_id = self
+            .recipient
+            .chat_id
+            .ok_or(ApiClientError::MissingChatId)?;
+
+        client.api_client.unpin_message(chat_id).await
+    }
 }
 
 pub trait SendMessageResultExt {
@@ -71,6 +108,17 @@ pub trait SendMessageResultExt {
         client: MaxBotClient,
         text: &str,
     ) -> impl std::future::Future<Output = ()> + Send;
+    fn wait_for_callback(
+        &self,
+        router: &impl AsRef<Interceptor>,
+        timeout: Duration,
+    ) -> impl std::future::Future<Output = api::types::Result<MessageCallbackUpdate>> + Send + 'static;
+    fn wait_for_callback_from(
+        &self,
+        router: &impl AsRef<Interceptor>,
+        user_id: i64,
+        timeout: Duration,
+    ) -> impl std::future::Future<Output = api::types::Result<MessageCallbackUpdate>> + Send + 'static;
 }
 
 impl SendMessageResultExt for SendMessageResult {
@@ -78,6 +126,85 @@ impl SendMessageResultExt for SendMessageResult {
         let message = NewMessageBody::new(text.into());
         let message_id = self.message.body.mid.clone();
 
-        let _ = client.api_client.edit_message(&message_id, &message).await;
+        let _ = match self.message.recipient.chat_id {
+            Some(chat_id) => {
+                client
+                    .api_client
+                    .edit_message_in_chat(chat_id, &message_id, &message)
+                    .await
+            }
+            None => client.api_client.edit_message(&message_id, &message).await,
+        };
+    }
+
+    // Ожидание регистрируется сразу, ещё до первого опроса future
+    fn wait_for_callback(
+        &self,
+        router: &impl AsRef<Interceptor>,
+        timeout: Duration,
+    ) -> impl std::future::Future<Output = api::types::Result<MessageCallbackUpdate>> + Send + 'static
+    {
+        let receiver = router
+            .as_ref()
+            .wait_for_callback(&self.message.body.mid, None);
+
+        wait_for(receiver, timeout)
+    }
+
+    fn wait_for_callback_from(
+        &self,
+        router: &impl AsRef<Interceptor>,
+        user_id: i64,
+        timeout: Duration,
+    ) -> impl std::future::Future<Output = api::types::Result<MessageCallbackUpdate>> + Send + 'static
+    {
+        let receiver = router
+            .as_ref()
+            .wait_for_callback(&self.message.body.mid, Some(user_id));
+
+        wait_for(receiver, timeout)
+    }
+}
+
+pub trait CallbackExt {
+    fn notify(
+        &self,
+        client: MaxBotClient,
+        text: &str,
+    ) -> impl std::future::Future<Output = api::types::Result<()>> + Send;
+    fn update_message(
+        &self,
+        client: MaxBotClient,
+        message: NewMessageBody,
+    ) -> impl std::future::Future<Output = api::types::Result<()>> + Send;
+}
+
+impl CallbackExt for Callback {
+    async fn notify(&self, client: MaxBotClient, text: &str) -> api::types::Result<()> {
+        let answer = CallbackAnswer {
+            message: None,
+            notification: Some(text.into()),
+        };
+
+        client
+            .api_client
+            .answer_callback(&self.callback_id, &answer)
+            .await
+    }
+
+    async fn update_message(
+        &self,
+        client: MaxBotClient,
+        message: NewMessageBody,
+    ) -> api::types::Result<()> {
+        let answer = CallbackAnswer {
+            message: Some(message),
+            notification: None,
+        };
+
+        client
+            .api_client
+            .answer_callback(&self.callback_id, &answer)
+            .await
     }
 }
@@ -0,0 +1,81 @@
+use tokio::sync::mpsc;
+
+use crate::{
+    api::{
+        models::{Subscription, SubscriptionRequestBody, Update},
+        types::Result,
+    },
+    client::MaxBotClient,
+    event_handler::EventHandler,
+};
+
+pub struct WebhookManager {
+    client: MaxBotClient,
+    subscription: SubscriptionRequestBody,
+}
+
+impl WebhookManager {
+    pub fn new(client: MaxBotClient, subscription: SubscriptionRequestBody) -> Self {
+        Self {
+            client,
+            subscription,
+        }
+    }
+
+    pub fn subscription(&self) -> &SubscriptionRequestBody {
+        &self.subscription
+    }
+
+    // Возвращает true, если подписки на сервере были изменены
+    pub async fn reconcile(&self) -> Result<bool> {
+        let registered = self.client.get_subscriptions().await?.subscriptions;
+        let mut changed = false;
+        let mut up_to_date = false;
+
+        for subscription in registered {
+            if self.matches(&subscription) {
+                up_to_date = true;
+                continue;
+            }
+
+            self.client.unsubscribe(&subscription.url).await?;
+            changed = true;
+        }
+
+        // Секрет не возвращается API, поэтому подписку с секретом всегда обновляем
+        if !up_to_date || self.subscription.secret.is_some() {
+            self.client.subscribe(&self.subscription).await?;
+            changed = true;
+        }
+
+        Ok(changed)
+    }
+
+    pub async fn unsubscribe(&self) -> Result<()> {
+        self.client.unsubscribe(&self.subscription.url).await
+    }
+
+    pub async fn fallback_to_polling(&self) -> Result<(EventHandler, mpsc::Receiver<Update>)> {
+        // Long polling недоступен, пока зарегистрирован вебхук
+        let registered = self.client.get_subscriptions().await?.subscriptions;
+        for subscription in registered {
+            self.client.unsubscribe(&subscription.url).await?;
+        }
+
+        Ok(EventHandler::new(self.client.clone()))
+    }
+
+    fn matches(&self, subscription: &Subscription) -> bool {
+        subscription.url == self.subscription.url
+            && sorted(&subscription.update_types) == sorted(&self.subscription.update_types)
+            && subscription.version == self.subscription.version
+    }
+}
+
+fn sorted(update_types: &Option<Vec<String>>) -> Option<Vec<&str>> {
+    update_types.as_ref().map(|types| {
+        let mut types: Vec<&str> = types.iter().map(String::as_str).collect();
+        types.sort_unstable();
+        types
+    })
+}
@@ -0,0 +1,184 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+};
+use tokio::{net::TcpListener, sync::mpsc};
+
+use crate::api::{error::ApiClientError, models::Update, types::Result};
+
+pub const SECRET_HEADER: &str = "X-Max-Bot-Api-Secret";
+
+type ErrorObserver = Arc<dyn Fn(&ApiClientError) + Send + Sync>;
+
+struct WebhookState {
+    secret: Option<String>,
+    event_sender: mpsc::Sender<Update>,
+    error_observer: Option<ErrorObserver>,
+}
+
+pub struct WebhookServer {
+    address: SocketAddr,
+    path: String,
+    secret: Option<String>,
+    event_sender: mpsc::Sender<Update>,
+    error_observer: Option<ErrorObserver>,
+}
+
+impl WebhookServer {
+    pub fn new(address: SocketAddr, secret: Option<String>) -> (Self, mpsc::Receiver<Update>) {
+        let (sender, receiver) = mpsc::channel(100);
+        (
+            Self {
+                address,
+                path: "/".to_string(),
+                secret,
+                event_sender: sender,
+                error_observer: None,
+            },
+            receiver,
+        )
+    }
+
+    pub fn set_path(&mut self, path: impl Into<String>) {
+        self.path = path.into();
+    }
+
+    // Получает обновления, которые не удалось разобрать и пришлось пропустить
+    pub fn on_error<F>(&mut self, observer: F)
+    where
+        F: Fn(&ApiClientError) + Send + Sync + 'static,
+    {
+        self.error_observer = Some(Arc::new(observer));
+    }
+
+    pub fn router(&self) -> Router {
+        let state = WebhookState {
+            secret: self.secret.clone(),
+            event_sender: self.event_sender.clone(),
+            error_observer: self.error_observer.clone(),
+        };
+
+        Router::new()
+            .route(&self.path, post(receive_update))
+            .with_state(Arc::new(state))
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        let listener = TcpListener::bind(self.address).await?;
+        axum::serve(listener, self.router()).await?;
+        Ok(())
+    }
+}
+
+async fn receive_update(
+    State(state): State<Arc<WebhookState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if let Some(secret) = &state.secret {
+        let provided = headers.get(SECRET_HEADER).map(|value| value.as_bytes());
+
+        if !provided.is_some_and(|provided| constant_time_eq(provided, secret.as_bytes())) {
+            return StatusCode::UNAUTHORIZED;
+        }
+    }
+
+    // Неизвестное обновление подтверждаем, иначе сервер будет повторять его доставку
+    let update: Update = match serde_json::from_slice(&body) {
+        Ok(update) => update,
+        Err(e) => {
+            if let Some(observer) = &state.error_observer {
+                observer(&e.into());
+            }
+            return StatusCode::OK;
+        }
+    };
+
+    // Сервер повторит доставку, если обработчики уже остановлены
+    match state.event_sender.send(update).await {
+        Ok(()) => StatusCode::OK,
+        Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+// Время сравнения не зависит от того, в каком байте секреты расходятся
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use tokio::sync::mpsc;
+    use tower::ServiceExt;
+
+    use super::{SECRET_HEADER, WebhookServer};
+    use crate::api::models::Update;
+
+    const UPDATE: &str = r#"{"update_type":"bot_started","timestamp":1,"chat_id":1,"user":{"user_id":2,"first_name":"Ivan","is_bot":false,"last_activity_time":0},"user_locale":"ru"}"#;
+
+    fn server() -> (WebhookServer, mpsc::Receiver<Update>) {
+        let address = "127.0.0.1:0".parse().unwrap();
+        WebhookServer::new(address, Some("secret".to_string()))
+    }
+
+    async fn post(server: &WebhookServer, secret: Option<&str>, body: &str) -> StatusCode {
+        let mut request = Request::post("/");
+        if let Some(secret) = secret {
+            request = request.header(SECRET_HEADER, secret);
+        }
+
+        let request = request.body(Body::from(body.to_string())).unwrap();
+        server.router().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_or_wrong_secret() {
+        let (server, mut receiver) = server();
+
+        assert_eq!(post(&server, None, UPDATE).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            post(&server, Some("secrets"), UPDATE).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            post(&server, Some("Secret"), UPDATE).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn delivers_valid_update() {
+        let (server, mut receiver) = server();
+
+        assert_eq!(post(&server, Some("secret"), UPDATE).await, StatusCode::OK);
+        assert!(matches!(
+            receiver.try_recv().unwrap(),
+            Update::BotStarted(update) if update.chat_id == 1
+        ));
+    }
+
+    #[tokio::test]
+    async fn acknowledges_and_reports_unknown_update() {
+        let (mut server, mut receiver) = server();
+        let errors = Arc::new(Mutex::new(0));
+        let counter = errors.clone();
+        server.on_error(move |_| *counter.lock().unwrap() += 1);
+
+        let body = r#"{"update_type":"something_new","timestamp":1}"#;
+        assert_eq!(post(&server, Some("secret"), body).await, StatusCode::OK);
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(*errors.lock().unwrap(), 1);
+    }
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures::TryStreamExt;
+use max_bot_api_client_rs::client::MaxBotClient;
+
+use common::{Responder, spawn_server};
+
+// Сервер отдаёт сообщения от новых к старым, начиная со времени from включительно
+fn messages_server(
+    messages: Vec<(&'static str, i64)>,
+    counts: Arc<Mutex<Vec<usize>>>,
+) -> Responder {
+    Arc::new(move |request| {
+        assert_eq!(request.method, "GET");
+        let (path, query) = request.path.split_once('?').unwrap();
+        assert_eq!(path, "/messages");
+
+        let query: HashMap<&str, &str> = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .collect();
+        let from = query.get("from").map(|from| from.parse::<i64>().unwrap());
+        let count: usize = query["count"].parse().unwrap();
+        counts.lock().unwrap().push(count);
+
+        let page: Vec<String> = messages
+            .iter()
+            .filter(|(_, timestamp)| from.is_none_or(|from| *timestamp <= from))
+            .take(count)
+            .map(|(mid, timestamp)| {
+                format!(
+                    r#"{{"recipient":{{"chat_id":1,"chat_type":"chat"}},"timestamp":{},"body":{{"mid":"{}","seq":1,"text":null}}}}"#,
+                    timestamp, mid
+                )
+            })
+            .collect();
+
+        Some((200, format!(r#"{{"messages":[{}]}}"#, page.join(","))))
+    })
+}
+
+async fn history(messages: Vec<(&'static str, i64)>, page_size: i32) -> (Vec<String>, Vec<usize>) {
+    let counts = Arc::new(Mutex::new(Vec::new()));
+    let address = spawn_server(messages_server(messages, counts.clone())).await;
+    let client = MaxBotClient::with_base_url("token".to_string(), &address).unwrap();
+
+    let mids = client
+        .message_history(1, page_size)
+        .map_ok(|message| message.body.mid)
+        .try_collect()
+        .await
+        .unwrap();
+
+    let counts = counts.lock().unwrap().clone();
+    (mids, counts)
+}
+
+#[tokio::test]
+async fn history_does_not_repeat_messages_on_page_boundary() {
+    let messages = vec![("a", 5), ("b", 4), ("c", 4), ("d", 3), ("e", 2)];
+    let (mids, _) = history(messages, 2).await;
+
+    assert_eq!(mids, ["a", "b", "c", "d", "e"]);
+}
+
+#[tokio::test]
+async fn history_widens_page_when_one_timestamp_fills_it() {
+    let messages = vec![("a", 5), ("b", 4), ("c", 4), ("d", 4), ("e", 3)];
+    let (mids, counts) = history(messages, 2).await;
+
+    assert_eq!(mids, ["a", "b", "c", "d", "e"]);
+    assert_eq!(counts, [2, 2, 2, 100]);
+}
@@ -0,0 +1,220 @@
+mod common;
+
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use max_bot_api_client_rs::api::error::ApiClientError;
+use max_bot_api_client_rs::api::models::{AttachmentRequest, ChunkedUploadOptions, UploadType};
+use max_bot_api_client_rs::client::MaxBotClient;
+
+use common::{Responder, spawn_server};
+
+const FILE_TOKEN: &str = "file-token";
+
+#[derive(Default)]
+struct UploadServerState {
+    ranges: Vec<String>,
+    dispositions: Vec<String>,
+    received: Vec<u8>,
+    dropped: bool,
+}
+
+// Сервер обрывает соединение на втором фрагменте, а затем подтверждает его лишь частично
+fn upload_server(total: usize, state: Arc<Mutex<UploadServerState>>) -> Responder {
+    Arc::new(move |request| {
+        if request.path.starts_with("/uploads") {
+            let host = request.header("host").unwrap();
+            let url = format!("http://{}/upload", host);
+            return Some((200, format!(r#"{{"url":"{}","token":null}}"#, url)));
+        }
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/upload");
+
+        let Some(range) = request.header("content-range") else {
+            // Загрузка одним запросом
+            return Some((200, format!(r#"{{"token":"{}"}}"#, FILE_TOKEN)));
+        };
+
+        let mut state = state.lock().unwrap();
+        state.ranges.push(range.to_string());
+        state
+            .dispositions
+            .push(request.header("content-disposition").unwrap().to_string());
+
+        let (start, end) = parse_range(range);
+        if start > 0 && !state.dropped {
+            state.dropped = true;
+            return None;
+        }
+
+        // Принимаем только часть второго фрагмента, клиент должен продолжить с подтверждённого места
+        let end = if start > 0 && end + 1 < total && state.received.len() == start {
+            start + 1
+        } else {
+            end
+        };
+
+        state.received.truncate(start);
+        state
+            .received
+            .extend_from_slice(&request.body[..end - start + 1]);
+
+        if end + 1 == total {
+            Some((200, format!(r#"{{"token":"{}"}}"#, FILE_TOKEN)))
+        } else {
+            Some((200, format!("{}-{}/{}", start, end, total)))
+        }
+    })
+}
+
+fn parse_range(range: &str) -> (usize, usize) {
+    let range = range.strip_prefix("bytes ").unwrap();
+    let (range, _) = range.split_once('/').unwrap();
+    let (start, end) = range.split_once('-').unwrap();
+
+    (start.parse().unwrap(), end.parse().unwrap())
+}
+
+#[tokio::test]
+async fn chunked_upload_resumes_from_acknowledged_offset() {
+    let data: Vec<u8> = (0..10).collect();
+    let state = Arc::new(Mutex::new(UploadServerState::default()));
+    let address = spawn_server(upload_server(data.len(), state.clone())).await;
+
+    let client = MaxBotClient::with_base_url("token".to_string(), &address).unwrap();
+    let options = ChunkedUploadOptions {
+        chunk_size: 4,
+        max_retries: 2,
+        retry_delay: Duration::from_millis(10),
+    };
+
+    let single = client
+        .upload_bytes(UploadType::File, "data.bin", data.clone())
+        .await
+        .unwrap();
+    let AttachmentRequest::File(single) = single else {
+        panic!("expected file attachment");
+    };
+
+    let mut upload = client
+        .start_chunked_upload(UploadType::File, "отчёт \"1\".bin", data.len() as u64)
+        .await
+        .unwrap();
+    let chunked = client
+        .resume_chunked_upload(&mut upload, &mut Cursor::new(data.clone()), &options)
+        .await
+        .unwrap();
+
+    assert_eq!(chunked.token, single.payload.token);
+    assert_eq!(upload.offset, data.len() as u64);
+
+    let state = state.lock().unwrap();
+    assert_eq!(
+        state.ranges,
+        [
+            "bytes 0-3/10",
+            "bytes 4-7/10",
+            "bytes 4-7/10",
+            "bytes 6-9/10"
+        ]
+    );
+    assert_eq!(state.received, data);
+    assert_eq!(
+        state.dispositions[0],
+        "attachment; filename=\"_____ \\\"1\\\".bin\"; \
+         filename*=UTF-8''%D0%BE%D1%82%D1%87%D1%91%D1%82%20%221%22.bin"
+    );
+}
+
+#[tokio::test]
+async fn chunked_upload_rejects_zero_chunk_size() {
+    let state = Arc::new(Mutex::new(UploadServerState::default()));
+    let address = spawn_server(upload_server(10, state.clone())).await;
+
+    let client = MaxBotClient::with_base_url("token".to_string(), &address).unwrap();
+    let options = ChunkedUploadOptions {
+        chunk_size: 0,
+        ..ChunkedUploadOptions::default()
+    };
+
+    let mut upload = client
+        .start_chunked_upload(UploadType::File, "data.bin", 10)
+        .await
+        .unwrap();
+    let result = client
+        .resume_chunked_upload(&mut upload, &mut Cursor::new(vec![0; 10]), &options)
+        .await;
+
+    assert!(result.is_err());
+    assert!(state.lock().unwrap().ranges.is_empty());
+}
+
+// Сервер отвечает на каждый фрагмент одним и тем же ответом
+fn fixed_chunk_server(
+    status: u16,
+    body: &'static str,
+    ranges: Arc<Mutex<Vec<String>>>,
+) -> Responder {
+    Arc::new(move |request| {
+        if request.path.starts_with("/uploads") {
+            let host = request.header("host").unwrap();
+            let url = format!("http://{}/upload", host);
+            return Some((200, format!(r#"{{"url":"{}","token":null}}"#, url)));
+        }
+
+        let range = request.header("content-range").unwrap();
+        ranges.lock().unwrap().push(range.to_string());
+        Some((status, body.to_string()))
+    })
+}
+
+async fn upload_with(responder: Responder) -> Result<(), ApiClientError> {
+    let address = spawn_server(responder).await;
+    let client = MaxBotClient::with_base_url("token".to_string(), &address).unwrap();
+    let options = ChunkedUploadOptions {
+        chunk_size: 4,
+        max_retries: 2,
+        retry_delay: Duration::from_millis(10),
+    };
+
+    let mut upload = client
+        .start_chunked_upload(UploadType::File, "data.bin", 10)
+        .await
+        .unwrap();
+    client
+        .resume_chunked_upload(&mut upload, &mut Cursor::new(vec![0; 10]), &options)
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn chunked_upload_rejects_acknowledgement_without_progress() {
+    let ranges = Arc::new(Mutex::new(Vec::new()));
+    let result = upload_with(fixed_chunk_server(200, "0-3/10", ranges.clone())).await;
+
+    assert!(matches!(result, Err(ApiClientError::UploadError(_))));
+    assert_eq!(*ranges.lock().unwrap(), ["bytes 0-3/10", "bytes 4-7/10"]);
+}
+
+#[tokio::test]
+async fn chunked_upload_retries_only_transient_failures() {
+    let ranges = Arc::new(Mutex::new(Vec::new()));
+    let result = upload_with(fixed_chunk_server(400, "bad range", ranges.clone())).await;
+
+    assert!(matches!(
+        result,
+        Err(ApiClientError::ApiError { status: 400, .. })
+    ));
+    assert_eq!(ranges.lock().unwrap().len(), 1);
+
+    let ranges = Arc::new(Mutex::new(Vec::new()));
+    let result = upload_with(fixed_chunk_server(503, "unavailable", ranges.clone())).await;
+
+    assert!(matches!(
+        result,
+        Err(ApiClientError::ApiError { status: 503, .. })
+    ));
+    assert_eq!(ranges.lock().unwrap().len(), 3);
+}